@@ -0,0 +1,30 @@
+//! Sets the `sysexit_sigemt` and `sysexit_siglost` configuration options on
+//! the targets that define the `SIGEMT` and `SIGLOST` signals, so that the
+//! list of targets is kept in one place.
+
+use std::env;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(sysexit_sigemt)");
+    println!("cargo:rustc-check-cfg=cfg(sysexit_siglost)");
+
+    let os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    let vendor = env::var("CARGO_CFG_TARGET_VENDOR").unwrap_or_default();
+
+    let sigemt = vendor == "apple"
+        || match os.as_str() {
+            "freebsd" | "dragonfly" | "netbsd" | "openbsd" | "solaris" | "illumos" | "hurd"
+            | "aix" | "cygwin" | "nto" | "horizon" | "rtems" | "vita" | "vxworks" => true,
+            "linux" => arch.starts_with("mips") || arch.starts_with("sparc"),
+            _ => false,
+        };
+    if sigemt {
+        println!("cargo:rustc-cfg=sysexit_sigemt");
+    }
+
+    if let "hurd" | "horizon" | "vxworks" = os.as_str() {
+        println!("cargo:rustc-cfg=sysexit_siglost");
+    }
+}
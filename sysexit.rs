@@ -8,7 +8,7 @@
 //! Exit statuses fall between 0 and 255 (inclusive), and codes greater than
 //! zero indicate failure.  The range 125–128 is reserved shell-specific
 //! statuses, including shell builtins and compound commands.  The range
-//! 129–154 is reserved fatal signals, explained below.
+//! 129–192 is reserved fatal signals, explained below.
 //!
 //! Usage:
 //!
@@ -16,12 +16,14 @@
 //! use std::process;
 //! use sysexit;
 //!
+//! # #[cfg(feature = "std")] {
 //! let exit_status = process::Command::new("sh")
 //!     .arg("-c").arg(format!("exit {}", 65))
 //!     .status()
 //!     .expect("failed to run sh(1)");
 //! let exit_code = sysexit::from_status(exit_status);
 //! println!("{}", exit_code);
+//! # }
 //! ```
 //!
 //! This outputs:
@@ -37,22 +39,95 @@
 //! This means that the `SIGHUP` (1) signal will be recognised as the exit code
 //! for the number 129.
 //!
+//! Signal numbers vary between platforms, and on Unix they are taken from
+//! `libc`.  Elsewhere, such as on Windows, the Linux and POSIX numbers are
+//! used.
+//!
 //! It should be pointed out that numeric exit codes are an absolute
 //! abomination, but we are stuck with them.
 //!
+//! The crate depends on the standard library through the default `std`
+//! feature, which provides the conversions from [`std::process::ExitStatus`]
+//! and `io` errors and everything that allocates.  Without it the crate is
+//! `no_std`, and the `Code` enum and its integer conversions remain.
+//!
+//! [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
+//!
 //! [sysexits(3)]: https://man.openbsd.org/sysexits.3
 
-#![allow(unknown_lints, cast_lossless, doc_markdown, match_same_arms)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+#![allow(unknown_lints, clippy::cast_lossless, clippy::doc_markdown, clippy::match_same_arms)]
 
+#[cfg(any(test, feature = "std"))]
+extern crate core;
+#[cfg(feature = "errno")]
+extern crate errno;
+#[cfg(any(test, target_family = "unix"))]
 extern crate libc;
-
-use std::fmt;
-use std::i8;
-use std::process;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+use core::fmt;
+#[cfg(feature = "std")]
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::error;
+#[cfg(feature = "std")]
 use std::io;
+#[cfg(feature = "std")]
+use std::io::Write;
+#[cfg(feature = "std")]
+use std::panic;
+#[cfg(feature = "std")]
+use std::process;
 
 const SIGBASE: i32 = i8::MAX as i32 + 1;
 
+const SIGMAX: i32 = 64;
+
+/// Signal numbers, as defined by the platform.
+#[cfg(target_family = "unix")]
+mod signum {
+    pub use libc::{
+        SIGABRT as ABRT, SIGALRM as ALRM, SIGBUS as BUS, SIGFPE as FPE, SIGHUP as HUP,
+        SIGILL as ILL, SIGINT as INT, SIGKILL as KILL, SIGPIPE as PIPE, SIGQUIT as QUIT,
+        SIGSEGV as SEGV, SIGTERM as TERM, SIGTRAP as TRAP, SIGUSR1 as USR1, SIGUSR2 as USR2,
+        SIGVTALRM as VTALRM,
+    };
+
+    // `libc` is missing `SIGEMT` on some of the targets with the signal, but
+    // its number is the same everywhere.
+    #[cfg(sysexit_sigemt)]
+    pub const EMT: i32 = 7;
+    #[cfg(sysexit_siglost)]
+    pub use libc::SIGLOST as LOST;
+}
+
+/// Signal numbers on every platform without Unix signals, such as Windows,
+/// wasm, and bare metal, where they are at most emulated, following Linux and
+/// POSIX.
+#[cfg(not(target_family = "unix"))]
+mod signum {
+    pub const HUP: i32 = 1;
+    pub const INT: i32 = 2;
+    pub const QUIT: i32 = 3;
+    pub const ILL: i32 = 4;
+    pub const TRAP: i32 = 5;
+    pub const ABRT: i32 = 6;
+    pub const BUS: i32 = 7;
+    pub const FPE: i32 = 8;
+    pub const KILL: i32 = 9;
+    pub const USR1: i32 = 10;
+    pub const SEGV: i32 = 11;
+    pub const USR2: i32 = 12;
+    pub const PIPE: i32 = 13;
+    pub const ALRM: i32 = 14;
+    pub const TERM: i32 = 15;
+    pub const VTALRM: i32 = 26;
+}
+
 /// A successful exit is always indicated by a status of 0, or
 /// [`exit::Success`].  Exit codes greater than zero indicates failure.
 ///
@@ -141,45 +216,183 @@ pub enum Code {
     /// the command is found but that a library it requires is not found.
     NotFound = 127,
 
+    /// The process was terminated by a fatal signal, without regard to which
+    /// one.  [`from_status_coarse`] collapses all signal terminations into
+    /// this single code, and the bare exit code 128 converts to it.
+    ///
+    /// [`from_status_coarse`]: fn.from_status_coarse.html
+    Signaled = SIGBASE,
+
     /// The `SIGHUP` signal is sent to a process when its controlling terminal
     /// is closed.
-    SIGHUP = SIGBASE + libc::SIGHUP,
+    SIGHUP = SIGBASE + signum::HUP,
 
     /// The `SIGINT` signal is sent to a process by its controlling terminal
     /// when a user wishes to interrupt the process.
-    SIGINT = SIGBASE + libc::SIGINT,
+    SIGINT = SIGBASE + signum::INT,
+
+    /// The `SIGQUIT` signal is sent to a process by its controlling terminal
+    /// when the user requests that the process quits and performs a core
+    /// dump.
+    SIGQUIT = SIGBASE + signum::QUIT,
+
+    /// The `SIGILL` signal is sent to a process when it attempts to execute
+    /// an illegal, malformed, or privileged instruction.
+    SIGILL = SIGBASE + signum::ILL,
+
+    /// The `SIGTRAP` signal is sent to a process when an exception or trap
+    /// occurs, such as a breakpoint being hit while it is being debugged.
+    SIGTRAP = SIGBASE + signum::TRAP,
+
+    /// The `SIGABRT` signal is sent to a process to tell it to abort, usually
+    /// by the process itself calling [abort(3)].
+    ///
+    /// [abort(3)]: https://man.openbsd.org/abort.3
+    SIGABRT = SIGBASE + signum::ABRT,
+
+    /// The `SIGBUS` signal is sent to a process when it causes a bus error,
+    /// such as accessing memory that is misaligned or does not physically
+    /// exist.
+    SIGBUS = SIGBASE + signum::BUS,
+
+    /// The `SIGFPE` signal is sent to a process when it performs an
+    /// erroneous arithmetic operation, such as division by zero.
+    SIGFPE = SIGBASE + signum::FPE,
 
     /// The `SIGKILL` signal is sent to a process to cause it to terminate
     /// immediately.  In contrast to `SIGTERM` and `SIGINT`, this signal cannot
     /// be caught or ignored, and the receiving process cannot perform any
     /// clean-up upon receiving this signal.
-    SIGKILL = SIGBASE + libc::SIGKILL,
+    SIGKILL = SIGBASE + signum::KILL,
+
+    /// The `SIGSEGV` signal is sent to a process when it makes an invalid
+    /// memory reference, also known as a segmentation fault.
+    SIGSEGV = SIGBASE + signum::SEGV,
 
     /// The `SIGPIPE` signal is sent to a process when it attempts to write to
     /// a pipe without a process connected to the other end.
-    SIGPIPE = SIGBASE + libc::SIGPIPE,
+    SIGPIPE = SIGBASE + signum::PIPE,
 
     /// The `SIGALRM` signal is sent to a process when the time limit specified
     /// in a call to a preceding alarm setting function (such as `setitimer`)
     /// elapses.
-    SIGALRM = SIGBASE + libc::SIGALRM,
+    SIGALRM = SIGBASE + signum::ALRM,
 
     /// The `SIGTERM` signal is sent to a process to request its termination.
     /// Unlike the `SIGKILL` signal, it can be caught and interpreted or
     /// ignored by the process.
-    SIGTERM = SIGBASE + libc::SIGTERM,
+    SIGTERM = SIGBASE + signum::TERM,
 
     /// The `SIGUSR1` signal, like `SIGUSR2`, is sent to a process to indicate
     /// a user-defined condition.
-    SIGUSR1 = SIGBASE + libc::SIGUSR1,
+    SIGUSR1 = SIGBASE + signum::USR1,
 
     /// The `SIGUSR2` signal, like `SIGUSR1`, is sent to a process to indicate
     /// a user-defined condition.
-    SIGUSR2 = SIGBASE + libc::SIGUSR2,
+    SIGUSR2 = SIGBASE + signum::USR2,
 
     /// The `SIGVTALRM` signal is sent to a process when the time limit
     /// specified for the virtual alarm elapses.
-    SIGVTALRM = SIGBASE + libc::SIGVTALRM,
+    SIGVTALRM = SIGBASE + signum::VTALRM,
+
+    /// The `SIGEMT` signal is sent to a process when an emulator trap
+    /// occurs.  It is only defined on BSD and some legacy systems.
+    #[cfg(sysexit_sigemt)]
+    SIGEMT = SIGBASE + signum::EMT,
+
+    /// The `SIGLOST` signal is sent to a process when a resource, such as a
+    /// file lock, is lost.  It is only defined on some legacy systems.
+    #[cfg(sysexit_siglost)]
+    SIGLOST = SIGBASE + signum::LOST,
+}
+
+/// The mnemonic name of each signal exit code, such as `"SIGTERM"` for
+/// [`sysexit::SIGTERM`].
+///
+/// [`sysexit::SIGTERM`]: enum.Code.html#variant.SIGTERM
+pub const SIGNAL_NAMES: &[(Code, &str)] = &[
+    (SIGHUP, "SIGHUP"),
+    (SIGINT, "SIGINT"),
+    (SIGQUIT, "SIGQUIT"),
+    (SIGILL, "SIGILL"),
+    (SIGTRAP, "SIGTRAP"),
+    (SIGABRT, "SIGABRT"),
+    (SIGBUS, "SIGBUS"),
+    (SIGFPE, "SIGFPE"),
+    (SIGKILL, "SIGKILL"),
+    (SIGSEGV, "SIGSEGV"),
+    (SIGUSR1, "SIGUSR1"),
+    (SIGUSR2, "SIGUSR2"),
+    (SIGPIPE, "SIGPIPE"),
+    (SIGALRM, "SIGALRM"),
+    (SIGTERM, "SIGTERM"),
+    (SIGVTALRM, "SIGVTALRM"),
+    #[cfg(sysexit_sigemt)]
+    (SIGEMT, "SIGEMT"),
+    #[cfg(sysexit_siglost)]
+    (SIGLOST, "SIGLOST"),
+];
+
+const ALL: &[Code] = &[
+    Success,
+    Failure,
+    Unknown,
+    Usage,
+    DataErr,
+    NoInput,
+    NoUser,
+    NoHost,
+    Unavailable,
+    Software,
+    OsErr,
+    OsFile,
+    CantCreat,
+    IoErr,
+    TempFail,
+    Protocol,
+    NoPerm,
+    Config,
+    NotExecutable,
+    NotFound,
+    Signaled,
+    SIGHUP,
+    SIGINT,
+    SIGQUIT,
+    SIGILL,
+    SIGTRAP,
+    SIGABRT,
+    SIGBUS,
+    SIGFPE,
+    SIGKILL,
+    SIGSEGV,
+    SIGUSR1,
+    SIGUSR2,
+    SIGPIPE,
+    SIGALRM,
+    SIGTERM,
+    SIGVTALRM,
+    #[cfg(sysexit_sigemt)]
+    SIGEMT,
+    #[cfg(sysexit_siglost)]
+    SIGLOST,
+];
+
+const DISCRIMINANTS: [i32; ALL.len()] = sorted_discriminants();
+
+const fn sorted_discriminants() -> [i32; ALL.len()] {
+    let mut out = [0; ALL.len()];
+    let mut i = 0;
+    while i < ALL.len() {
+        let n = ALL[i] as i32;
+        let mut j = i;
+        while j > 0 && out[j - 1] > n {
+            out[j] = out[j - 1];
+            j -= 1;
+        }
+        out[j] = n;
+        i += 1;
+    }
+    out
 }
 
 /// Converts an `i32` primitive integer to an exit code.
@@ -211,15 +424,27 @@ impl From<i32> for Code {
             126 => NotExecutable,
             127 => NotFound,
 
-            _ if n == SIGBASE + libc::SIGHUP => SIGHUP,
-            _ if n == SIGBASE + libc::SIGINT => SIGINT,
-            _ if n == SIGBASE + libc::SIGKILL => SIGKILL,
-            _ if n == SIGBASE + libc::SIGUSR1 => SIGUSR1,
-            _ if n == SIGBASE + libc::SIGUSR2 => SIGUSR2,
-            _ if n == SIGBASE + libc::SIGPIPE => SIGPIPE,
-            _ if n == SIGBASE + libc::SIGALRM => SIGALRM,
-            _ if n == SIGBASE + libc::SIGTERM => SIGTERM,
-            _ if n == SIGBASE + libc::SIGVTALRM => SIGVTALRM,
+            _ if n == SIGBASE => Signaled,
+            _ if n == SIGBASE + signum::HUP => SIGHUP,
+            _ if n == SIGBASE + signum::INT => SIGINT,
+            _ if n == SIGBASE + signum::QUIT => SIGQUIT,
+            _ if n == SIGBASE + signum::ILL => SIGILL,
+            _ if n == SIGBASE + signum::TRAP => SIGTRAP,
+            _ if n == SIGBASE + signum::ABRT => SIGABRT,
+            _ if n == SIGBASE + signum::BUS => SIGBUS,
+            _ if n == SIGBASE + signum::FPE => SIGFPE,
+            _ if n == SIGBASE + signum::KILL => SIGKILL,
+            _ if n == SIGBASE + signum::SEGV => SIGSEGV,
+            _ if n == SIGBASE + signum::USR1 => SIGUSR1,
+            _ if n == SIGBASE + signum::USR2 => SIGUSR2,
+            _ if n == SIGBASE + signum::PIPE => SIGPIPE,
+            _ if n == SIGBASE + signum::ALRM => SIGALRM,
+            _ if n == SIGBASE + signum::TERM => SIGTERM,
+            _ if n == SIGBASE + signum::VTALRM => SIGVTALRM,
+            #[cfg(sysexit_sigemt)]
+            _ if n == SIGBASE + signum::EMT => SIGEMT,
+            #[cfg(sysexit_siglost)]
+            _ if n == SIGBASE + signum::LOST => SIGLOST,
 
             _ => Unknown,
         }
@@ -239,13 +464,16 @@ impl From<Option<i32>> for Code {
 /// [`ExitStatus::code()`] value.
 ///
 /// On Unix, if the process was terminated by a fatal signal, the corresponding
-/// signal exit code is returned.  If the passed exit status cannot be
+/// signal exit code is returned.  On Windows, if the process was terminated by
+/// an unhandled exception, such as an access violation, the exit code of the
+/// closest signal or failure is returned.  If the passed exit status cannot be
 /// determined, `exit::Unknown` (2) is returned.
 ///
 /// [`std::process::ExitStatus`]:
 /// https://doc.rust-lang.org/std/process/struct.ExitStatus.html
 /// [`ExitStatus::code()`]:
 /// https://doc.rust-lang.org/std/process/struct.ExitStatus.html#method.code
+#[cfg(feature = "std")]
 impl From<process::ExitStatus> for Code {
     fn from(status: process::ExitStatus) -> Self {
         let n = platform_exit_code(status).unwrap_or(Code::Unknown as i32);
@@ -253,6 +481,7 @@ impl From<process::ExitStatus> for Code {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<io::ErrorKind> for Code {
     fn from(kind: io::ErrorKind) -> Self {
         use io::ErrorKind::*;
@@ -270,249 +499,2949 @@ impl From<io::ErrorKind> for Code {
     }
 }
 
-/// Provides a user-friendly explanation of the exit code.
-impl fmt::Display for Code {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use self::Code::*;
-
-        let reason = match *self {
-            Success => "success",
-            Failure => "failure",
-            Unknown => "unknown",
-            Usage => "usage",
-            DataErr => "data",
-            NoInput => "no input",
-            NoUser => "no user",
-            NoHost => "no host",
-            Unavailable => "unavailable",
-            Software => "software",
-            OsErr => "os err",
-            OsFile => "os file",
-            CantCreat => "cannot create",
-            IoErr => "i/o error",
-            TempFail => "temporary failure",
-            Protocol => "protocol",
-            NoPerm => "permission denied",
-            Config => "config",
-
-            NotExecutable => "not executable",
-            NotFound => "not found",
-
-            SIGHUP => "hangup signal",
-            SIGINT => "terminal interrupt signal",
-            SIGKILL => "kill signal",
-            SIGPIPE => "write on a pipe with no one to read it signal",
-            SIGALRM => "alarm clock signal",
-            SIGTERM => "termination signal",
-            SIGUSR1 => "user-defined signal 1",
-            SIGUSR2 => "user-defined signal 2",
-            SIGVTALRM => "virtual timer expired signal",
-        };
-
-        write!(f, "{} ({})", reason, *self as i32)
+/// Converts an exit code to its number, the inverse of the conversion from
+/// `i32`.
+impl From<Code> for i32 {
+    fn from(code: Code) -> Self {
+        code as i32
     }
 }
 
-#[cfg(target_family = "unix")]
-fn platform_exit_code(status: process::ExitStatus) -> Option<i32> {
-    use std::os::unix::process::ExitStatusExt;
-    status.code().or_else(|| status.signal())
-}
-
-#[cfg(not(target_family = "unix"))]
-fn platform_exit_code(status: process::ExitStatus) -> Option<i32> {
-    status.code()
-}
-
-pub use self::Code::*;
-
-/// Converts [`std::process::ExitStatus`] to [`sysexit::Code`].
-///
-/// On Unix, if the process was terminated by a fatal signal, the corresponding
-/// signal exit code is returned.  If the passed exit status cannot be
-/// determined, [`sysexit::Unknown`] (2) is returned.
-///
-/// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
-/// [`sysexit::Code`]: enum.Code.html
-/// [`sysexit::Unknown`]: enum.Code.html#variant.Unknown
-pub fn from_status(status: process::ExitStatus) -> Code {
-    Code::from(status)
-}
-
-/// Determines if the provided [`std::process::ExitStatus`] was successful.
+/// Converts an exit code to the closest [`std::io::ErrorKind`], the inverse
+/// of the conversion from `io::ErrorKind`.
 ///
-/// Example:
+/// The conversion is lossy: several error kinds share one exit code, so only
+/// one representative kind comes back, and codes with no sensible I/O
+/// analogue, such as signals, `Usage` or `Config`, become
+/// `io::ErrorKind::Other`.
 ///
 /// ```
-/// use std::process;
+/// use std::io;
 /// use sysexit;
 ///
-/// let exit_status = process::Command::new("true")
-///     .status()
-///     .expect("failed to run true(1)");
-/// assert!(sysexit::is_success(exit_status));
+/// assert_eq!(io::ErrorKind::from(sysexit::NoPerm), io::ErrorKind::PermissionDenied);
+/// assert_eq!(io::ErrorKind::from(sysexit::Usage), io::ErrorKind::Other);
 /// ```
 ///
-/// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
-pub fn is_success(status: process::ExitStatus) -> bool {
-    Code::from(status) == Success
+/// [`std::io::ErrorKind`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html
+#[cfg(feature = "std")]
+impl From<Code> for io::ErrorKind {
+    fn from(code: Code) -> Self {
+        use io::ErrorKind::*;
+        match code {
+            Code::OsFile => NotFound,
+            Code::NoPerm => PermissionDenied,
+            Code::Unavailable => AddrNotAvailable,
+            Code::Protocol => ConnectionReset,
+            Code::CantCreat => AlreadyExists,
+            Code::DataErr => InvalidData,
+            _ => Other,
+        }
+    }
 }
 
-/// Determines if the provided [`std::process::ExitStatus`] was unsuccessful.
-///
-/// Example:
-///
-/// ```
-/// use std::process;
-/// use sysexit;
-///
-/// let exit_status = process::Command::new("false")
-///     .status()
-///     .expect("failed to run false(1)");
-/// assert!(sysexit::is_error(exit_status));
-/// ```
-///
-/// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
-pub fn is_error(status: process::ExitStatus) -> bool {
-    !is_success(status)
-}
+impl Code {
+    /// Renders the exit code as a row in a Markdown table, with the columns
+    /// being the number, the mnemonic name, the category, and a short
+    /// explanation:
+    ///
+    /// ```
+    /// use sysexit;
+    ///
+    /// assert_eq!(sysexit::IoErr.to_markdown_row(), "| 74 | EX_IOERR | system | i/o error |");
+    /// ```
+    ///
+    /// See [`markdown_table`] for rendering all the exit codes.
+    ///
+    /// [`markdown_table`]: fn.markdown_table.html
+    #[cfg(feature = "std")]
+    pub fn to_markdown_row(self) -> String {
+        format!(
+            "| {} | {} | {} | {} |",
+            self as i32,
+            self.name(),
+            self.category(),
+            self.reason()
+        )
+    }
 
-/// Tests if the provided exit code is reserved, and has a special meaning in
-/// shells.
-pub fn is_reserved(n: i32) -> bool {
-    (Success as i32 <= n && n <= Unknown as i32) || (Usage as i32 <= n && n <= Config as i32)
-        || (NotExecutable as i32 <= n && n <= SIGVTALRM as i32)
-}
+    /// Renders the exit code as a shell `exit` command, with a comment
+    /// explaining it.  For signal codes the comment also names the signal:
+    ///
+    /// ```
+    /// use sysexit;
+    ///
+    /// assert_eq!(sysexit::IoErr.to_shell_exit(), "exit 74  # i/o error");
+    /// assert_eq!(
+    ///     sysexit::SIGTERM.to_shell_exit(),
+    ///     "exit 143  # termination signal (SIGTERM)"
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_shell_exit(self) -> String {
+        match (self, self.signal_name()) {
+            (_, Some(name)) => format!("exit {}  # {} ({})", self as i32, self.reason(), name),
+            (_, None) => format!("exit {}  # {}", self as i32, self.reason()),
+        }
+    }
 
-/// Test if provided exit code is valid, that is within the 0–255 (inclusive)
-/// range.
-pub fn is_valid(n: i32) -> bool {
-    0 <= n && n <= 255
-}
+    /// Returns the mnemonic name of the signal, such as `"SIGTERM"`, if the
+    /// exit code is a signal exit code.  The names are looked up in
+    /// [`SIGNAL_NAMES`].
+    ///
+    /// [`SIGNAL_NAMES`]: constant.SIGNAL_NAMES.html
+    pub const fn signal_name(self) -> Option<&'static str> {
+        let mut i = 0;
+        while i < SIGNAL_NAMES.len() {
+            let (code, name) = SIGNAL_NAMES[i];
+            if code as i32 == self as i32 {
+                return Some(name);
+            }
+            i += 1;
+        }
+        None
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Determines if the exit code is that of a process terminated by a
+    /// fatal signal _N_, which is the exit code 128 + _N_.  This is true for
+    /// the signal codes, such as `SIGTERM`, but not for `Signaled`, where the
+    /// signal is not known.
+    ///
+    /// ```
+    /// use sysexit;
+    ///
+    /// assert!(sysexit::SIGTERM.is_signal());
+    /// assert!(!sysexit::Signaled.is_signal());
+    /// assert!(!sysexit::IoErr.is_signal());
+    /// ```
+    pub const fn is_signal(self) -> bool {
+        self.signal_number().is_some()
+    }
 
-    #[test]
-    fn sigbase() {
-        assert_eq!(SIGBASE, 128);
+    /// Returns the number _N_ of the fatal signal that terminated the
+    /// process, such as 15 for `SIGTERM`, if the exit code is a signal exit
+    /// code.  This is the inverse of encoding the signal as the exit code
+    /// 128 + _N_.
+    ///
+    /// ```
+    /// use sysexit;
+    ///
+    /// assert_eq!(sysexit::SIGTERM.signal_number(), Some(15));
+    /// assert_eq!(sysexit::IoErr.signal_number(), None);
+    /// ```
+    pub const fn signal_number(self) -> Option<i32> {
+        match self as i32 {
+            n if n > SIGBASE => Some(n - SIGBASE),
+            _ => None,
+        }
     }
 
-    #[test]
-    fn from_i32() {
-        assert_eq!(Code::from(0), Success);
-        assert_eq!(Code::from(1), Failure);
-        assert_eq!(Code::from(2), Unknown);
+    /// Returns the category of the exit code, by the band of exit codes it
+    /// falls into:
+    ///
+    /// ```
+    /// use sysexit::{self, Category};
+    ///
+    /// assert_eq!(sysexit::Success.category(), Category::Success);
+    /// assert_eq!(sysexit::Failure.category(), Category::Generic);
+    /// assert_eq!(sysexit::IoErr.category(), Category::SysExit);
+    /// assert_eq!(sysexit::NotFound.category(), Category::Shell);
+    /// assert_eq!(sysexit::SIGTERM.category(), Category::Signal);
+    /// ```
+    pub const fn category(self) -> Category {
+        match self as i32 {
+            0 => Category::Success,
+            1..=2 => Category::Generic,
+            64..=78 => Category::SysExit,
+            126..=SIGBASE => Category::Shell,
+            _ => Category::Signal,
+        }
+    }
 
-        assert_eq!(Code::from(64), Usage);
-        assert_eq!(Code::from(65), DataErr);
-        assert_eq!(Code::from(66), NoInput);
-        assert_eq!(Code::from(67), NoUser);
-        assert_eq!(Code::from(68), NoHost);
-        assert_eq!(Code::from(69), Unavailable);
-        assert_eq!(Code::from(70), Software);
-        assert_eq!(Code::from(71), OsErr);
-        assert_eq!(Code::from(72), OsFile);
-        assert_eq!(Code::from(73), CantCreat);
-        assert_eq!(Code::from(74), IoErr);
-        assert_eq!(Code::from(75), TempFail);
-        assert_eq!(Code::from(76), Protocol);
-        assert_eq!(Code::from(77), NoPerm);
-        assert_eq!(Code::from(78), Config);
+    /// Returns the short, machine-readable name of the exit code, such as
+    /// `"EX_IOERR"` or `"SIGTERM"`, for use in structured logs and metrics
+    /// labels.  Unlike `Display`, the name has no spaces or numeric suffix.
+    ///
+    /// Only the codes defined by `sysexits.h` have an `EX_` name.  The other
+    /// codes are named without the prefix, such as `"NOTFOUND"`.  Every name is
+    /// accepted back by `FromStr`.
+    ///
+    /// ```
+    /// use sysexit;
+    ///
+    /// assert_eq!(sysexit::IoErr.name(), "EX_IOERR");
+    /// assert_eq!(sysexit::NotFound.name(), "NOTFOUND");
+    /// assert_eq!(sysexit::SIGTERM.name(), "SIGTERM");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            Success => "EX_OK",
+            Failure => "FAILURE",
+            Unknown => "UNKNOWN",
+            Usage => "EX_USAGE",
+            DataErr => "EX_DATAERR",
+            NoInput => "EX_NOINPUT",
+            NoUser => "EX_NOUSER",
+            NoHost => "EX_NOHOST",
+            Unavailable => "EX_UNAVAILABLE",
+            Software => "EX_SOFTWARE",
+            OsErr => "EX_OSERR",
+            OsFile => "EX_OSFILE",
+            CantCreat => "EX_CANTCREAT",
+            IoErr => "EX_IOERR",
+            TempFail => "EX_TEMPFAIL",
+            Protocol => "EX_PROTOCOL",
+            NoPerm => "EX_NOPERM",
+            Config => "EX_CONFIG",
+
+            NotExecutable => "NOEXEC",
+            NotFound => "NOTFOUND",
+            Signaled => "SIGNALED",
+
+            _ => self.signal_name().unwrap_or("UNKNOWN"),
+        }
+    }
 
-        assert_eq!(Code::from(126), NotExecutable);
-        assert_eq!(Code::from(127), NotFound);
+    /// Synthesizes an `io::Error` representing the exit code, with the
+    /// closest `io::ErrorKind` and the `Display` form of the code as its
+    /// message.  See the conversion to `io::ErrorKind` for how lossy this is.
+    ///
+    /// ```
+    /// use std::io;
+    /// use sysexit;
+    ///
+    /// let err = sysexit::OsFile.to_io_error();
+    /// assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    /// assert_eq!(err.to_string(), "os file (72)");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_io_error(self) -> io::Error {
+        io::Error::new(io::ErrorKind::from(self), self.to_string())
+    }
 
-        assert_eq!(Code::from(129), SIGHUP);
-        assert_eq!(Code::from(130), SIGINT);
-        assert_eq!(Code::from(137), SIGKILL);
-        assert_eq!(Code::from(138), SIGUSR1);
-        assert_eq!(Code::from(140), SIGUSR2);
-        assert_eq!(Code::from(141), SIGPIPE);
-        assert_eq!(Code::from(142), SIGALRM);
-        assert_eq!(Code::from(143), SIGTERM);
-        assert_eq!(Code::from(154), SIGVTALRM);
+    /// Terminates the process with the exit code, after flushing stdout and
+    /// stderr.  This is [`std::process::exit`] with the intent spelled out,
+    /// and like it, does not run destructors.
+    ///
+    /// ```no_run
+    /// use sysexit;
+    ///
+    /// sysexit::Usage.exit();
+    /// ```
+    ///
+    /// [`std::process::exit`]: https://doc.rust-lang.org/std/process/fn.exit.html
+    #[cfg(feature = "std")]
+    pub fn exit(self) -> ! {
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+        process::exit(self as i32)
+    }
 
-        assert_eq!(Code::from(-1), Unknown);
-        assert_eq!(Code::from(128), Unknown);
-        assert_eq!(Code::from(162), Unknown);
+    /// Determines if the exit code survives being formatted with `Display`
+    /// and parsed back with `FromStr`.  This holds for every exit code.
+    ///
+    /// ```
+    /// use sysexit;
+    ///
+    /// assert!(sysexit::all().iter().all(|code| code.roundtrips_via_display()));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn roundtrips_via_display(self) -> bool {
+        self.to_string().parse::<Code>() == Ok(self)
     }
 
-    fn exit_status(code: i32) -> process::ExitStatus {
-        process::Command::new("sh")
-            .arg("-c")
-            .arg(format!("exit {}", code))
-            .status()
-            .expect("failed to run sh(1)")
+    /// Determines if the exit code is a failure that can safely be cached,
+    /// for example by a build system, because running the program again
+    /// with the same input would fail the same way.
+    ///
+    /// This holds for the failures that are [deterministic]: `Usage`,
+    /// `DataErr`, `NoInput`, `NoUser`, `Software`, `OsFile`, `CantCreat`,
+    /// `NoPerm`, `Config`, `NotExecutable`, and `NotFound`.  Transient
+    /// failures such as `TempFail` and `Unavailable`, and termination by a
+    /// signal, should not be cached.
+    ///
+    /// [deterministic]: #method.is_deterministic
+    pub fn is_cacheable_failure(self) -> bool {
+        self != Success && self.is_deterministic()
     }
 
-    #[test]
-    fn from_exitstatus() {
-        assert_eq!(Code::from(exit_status(0)), Success);
-        assert_eq!(Code::from(exit_status(1)), Failure);
-        assert_eq!(Code::from(exit_status(2)), Unknown);
+    /// Suggests an HTTP status code for reporting the outcome of a
+    /// subprocess, for example from a health endpoint.
+    ///
+    /// | Exit code                                | HTTP status |
+    /// |------------------------------------------|-------------|
+    /// | `Success`                                | 200         |
+    /// | `Usage`, `DataErr`                       | 400         |
+    /// | `NoPerm`                                 | 403         |
+    /// | `NoInput`, `NoUser`                      | 404         |
+    /// | `NoHost`, `Protocol`                     | 502         |
+    /// | `Unavailable`, `TempFail`                | 503         |
+    /// | All other codes, including signals       | 500         |
+    pub fn to_http_status(self) -> u16 {
+        match self {
+            Success => 200,
+            Usage | DataErr => 400,
+            NoPerm => 403,
+            NoInput | NoUser => 404,
+            NoHost | Protocol => 502,
+            Unavailable | TempFail => 503,
+            _ => 500,
+        }
+    }
 
-        assert_eq!(Code::from(exit_status(64)), Usage);
-        assert_eq!(Code::from(exit_status(65)), DataErr);
-        assert_eq!(Code::from(exit_status(66)), NoInput);
-        assert_eq!(Code::from(exit_status(67)), NoUser);
-        assert_eq!(Code::from(exit_status(68)), NoHost);
-        assert_eq!(Code::from(exit_status(69)), Unavailable);
-        assert_eq!(Code::from(exit_status(70)), Software);
-        assert_eq!(Code::from(exit_status(71)), OsErr);
-        assert_eq!(Code::from(exit_status(72)), OsFile);
-        assert_eq!(Code::from(exit_status(73)), CantCreat);
-        assert_eq!(Code::from(exit_status(74)), IoErr);
-        assert_eq!(Code::from(exit_status(75)), TempFail);
-        assert_eq!(Code::from(exit_status(76)), Protocol);
-        assert_eq!(Code::from(exit_status(77)), NoPerm);
-        assert_eq!(Code::from(exit_status(78)), Config);
+    /// Determines if a batch of work should be stopped when an item fails
+    /// with the exit code, rather than skipping the item and continuing.
+    ///
+    /// This is the case for `Usage` and `Config`, which will affect every
+    /// item until fixed, and for the signals `SIGINT`, `SIGTERM`, and
+    /// `SIGHUP`, which indicate that the user wants to stop everything.
+    /// Other exit codes, such as `DataErr`, are considered specific to the
+    /// failing item.
+    pub fn is_fatal_for_batch(self) -> bool {
+        matches!(self, Usage | Config | SIGINT | SIGTERM | SIGHUP)
+    }
 
-        assert_eq!(Code::from(exit_status(126)), NotExecutable);
-        assert_eq!(Code::from(exit_status(127)), NotFound);
+    /// Suggests a URL to documentation explaining the exit code, for example
+    /// for a program to print “see <url> for details” when it fails.
+    ///
+    /// The [sysexits(3)] codes link to their entry in the manual page,
+    /// signal codes to [signal(3)], shell codes to the bash manual, and
+    /// `Success` and `Failure` to [exit(3)].  `Unknown` has no
+    /// documentation.  The URLs are believed to be stable, but this cannot
+    /// be guaranteed for external websites.
+    ///
+    /// [sysexits(3)]: https://man.openbsd.org/sysexits.3
+    /// [signal(3)]: https://man.openbsd.org/signal.3
+    /// [exit(3)]: https://man.openbsd.org/exit.3
+    pub fn help_url(self) -> Option<&'static str> {
+        match self {
+            Success | Failure => Some("https://man.openbsd.org/exit.3"),
+            Unknown => None,
+            Usage => Some("https://man.openbsd.org/sysexits.3#EX_USAGE"),
+            DataErr => Some("https://man.openbsd.org/sysexits.3#EX_DATAERR"),
+            NoInput => Some("https://man.openbsd.org/sysexits.3#EX_NOINPUT"),
+            NoUser => Some("https://man.openbsd.org/sysexits.3#EX_NOUSER"),
+            NoHost => Some("https://man.openbsd.org/sysexits.3#EX_NOHOST"),
+            Unavailable => Some("https://man.openbsd.org/sysexits.3#EX_UNAVAILABLE"),
+            Software => Some("https://man.openbsd.org/sysexits.3#EX_SOFTWARE"),
+            OsErr => Some("https://man.openbsd.org/sysexits.3#EX_OSERR"),
+            OsFile => Some("https://man.openbsd.org/sysexits.3#EX_OSFILE"),
+            CantCreat => Some("https://man.openbsd.org/sysexits.3#EX_CANTCREAT"),
+            IoErr => Some("https://man.openbsd.org/sysexits.3#EX_IOERR"),
+            TempFail => Some("https://man.openbsd.org/sysexits.3#EX_TEMPFAIL"),
+            Protocol => Some("https://man.openbsd.org/sysexits.3#EX_PROTOCOL"),
+            NoPerm => Some("https://man.openbsd.org/sysexits.3#EX_NOPERM"),
+            Config => Some("https://man.openbsd.org/sysexits.3#EX_CONFIG"),
+            NotExecutable | NotFound | Signaled => {
+                Some("https://www.gnu.org/software/bash/manual/html_node/Exit-Status.html")
+            }
+            _ => Some("https://man.openbsd.org/signal.3"),
+        }
+    }
 
-        assert_eq!(Code::from(exit_status(129)), SIGHUP);
-        assert_eq!(Code::from(exit_status(130)), SIGINT);
-        assert_eq!(Code::from(exit_status(137)), SIGKILL);
-        assert_eq!(Code::from(exit_status(138)), SIGUSR1);
-        assert_eq!(Code::from(exit_status(140)), SIGUSR2);
-        assert_eq!(Code::from(exit_status(141)), SIGPIPE);
-        assert_eq!(Code::from(exit_status(142)), SIGALRM);
-        assert_eq!(Code::from(exit_status(143)), SIGTERM);
-        assert_eq!(Code::from(exit_status(154)), SIGVTALRM);
+    /// Returns a short symbol for indicating the outcome of the last command
+    /// in a shell prompt: nothing for success, `"⚡"` if the command was
+    /// terminated by a signal, and `"✗"` for any other failure.
+    pub fn prompt_indicator(self) -> &'static str {
+        match self {
+            Success => "",
+            code if code as i32 >= SIGBASE => "⚡",
+            _ => "✗",
+        }
     }
 
-    #[test]
-    fn success() {
-        assert!(is_success(exit_status(0)));
-        assert!(!is_success(exit_status(1)));
+    /// Returns a stable hex colour for displaying the exit code in graphical
+    /// interfaces, chosen by the category of the exit code:
+    ///
+    /// | Category                  | Colour    |
+    /// |---------------------------|-----------|
+    /// | Success                   | `#2ecc71` |
+    /// | Generic failure (1–2)     | `#e67e22` |
+    /// | [sysexits(3)] (64–78)     | `#e74c3c` |
+    /// | Shell (126–128)           | `#9b59b6` |
+    /// | Signal (129 and above)    | `#c0392b` |
+    ///
+    /// [sysexits(3)]: https://man.openbsd.org/sysexits.3
+    pub fn hex_color(self) -> &'static str {
+        match self.category() {
+            Category::Success => "#2ecc71",
+            Category::Generic => "#e67e22",
+            Category::SysExit => "#e74c3c",
+            Category::Shell => "#9b59b6",
+            Category::Signal => "#c0392b",
+        }
     }
 
-    #[test]
-    fn error() {
-        assert!(is_error(exit_status(1)));
-        assert!(!is_error(exit_status(0)));
+    /// Determines if the exit code is expected to be the same when the
+    /// program is run again with the same input, which can be used to decide
+    /// between retrying and failing fast.
+    ///
+    /// These exit codes are deterministic: `Success`, `Usage`, `DataErr`,
+    /// `NoInput`, `NoUser`, `Software`, `OsFile`, `CantCreat`, `NoPerm`,
+    /// `Config`, `NotExecutable`, and `NotFound`.
+    ///
+    /// All other exit codes are not: transient conditions such as `TempFail`,
+    /// `Unavailable`, `NoHost`, `Protocol`, `IoErr`, and `OsErr`, which
+    /// depend on the network or on system resources; termination by a
+    /// signal, such as `SIGKILL` from the out-of-memory killer; and the
+    /// generic `Failure` and `Unknown`, which reveal too little to tell.
+    pub fn is_deterministic(self) -> bool {
+        matches!(
+            self,
+            Success
+                | Usage
+                | DataErr
+                | NoInput
+                | NoUser
+                | Software
+                | OsFile
+                | CantCreat
+                | NoPerm
+                | Config
+                | NotExecutable
+                | NotFound
+        )
     }
 
-    #[test]
-    fn reserved() {
-        for n in 0..512 {
-            println!("{}", n);
-            match n {
-                0...2 => assert!(is_reserved(n)),
-                64...78 => assert!(is_reserved(n)),
-                126...154 => assert!(is_reserved(n)),
-                n => assert!(!is_reserved(n)),
-            }
+    /// Lists the conventional meanings of the exit code, ordered from the
+    /// most to the least likely, since many exit codes are used for more
+    /// than one purpose.  The first entry is the explanation given by the
+    /// `Display` implementation.
+    ///
+    /// ```
+    /// use sysexit;
+    ///
+    /// assert_eq!(
+    ///     sysexit::NotFound.likely_meanings(),
+    ///     &["not found", "library not found"]
+    /// );
+    /// assert_eq!(sysexit::IoErr.likely_meanings(), &["i/o error"]);
+    /// ```
+    pub fn likely_meanings(self) -> &'static [&'static str] {
+        match self {
+            Success => &["success"],
+            Failure => &["failure", "false condition"],
+            Unknown => &["unknown", "misuse of shell builtin", "incorrect usage"],
+            Usage => &["usage"],
+            DataErr => &["data"],
+            NoInput => &["no input"],
+            NoUser => &["no user"],
+            NoHost => &["no host"],
+            Unavailable => &["unavailable"],
+            Software => &["software"],
+            OsErr => &["os err"],
+            OsFile => &["os file"],
+            CantCreat => &["cannot create"],
+            IoErr => &["i/o error"],
+            TempFail => &["temporary failure"],
+            Protocol => &["protocol"],
+            NoPerm => &["permission denied"],
+            Config => &["config"],
+
+            NotExecutable => &["not executable", "permission denied"],
+            NotFound => &["not found", "library not found"],
+            Signaled => &["signaled", "invalid argument to exit"],
+
+            SIGHUP => &["hangup signal"],
+            SIGINT => &["terminal interrupt signal"],
+            SIGQUIT => &["quit signal"],
+            SIGILL => &["illegal instruction signal"],
+            SIGTRAP => &["trace trap signal"],
+            SIGABRT => &["abort signal"],
+            SIGBUS => &["bus error signal"],
+            SIGFPE => &["floating-point exception signal"],
+            SIGKILL => &["kill signal", "out of memory"],
+            SIGSEGV => &["segmentation fault signal"],
+            SIGPIPE => &["write on a pipe with no one to read it signal"],
+            SIGALRM => &["alarm clock signal"],
+            SIGTERM => &["termination signal"],
+            SIGUSR1 => &["user-defined signal 1"],
+            SIGUSR2 => &["user-defined signal 2"],
+            SIGVTALRM => &["virtual timer expired signal"],
+            #[cfg(sysexit_sigemt)]
+            SIGEMT => &["emulator trap signal"],
+            #[cfg(sysexit_siglost)]
+            SIGLOST => &["resource lost signal"],
+        }
+    }
+
+    fn reason(self) -> &'static str {
+        self.likely_meanings()[0]
+    }
+
+    #[cfg(feature = "std")]
+    fn severity(self) -> u8 {
+        match self.category() {
+            Category::Success => 0,
+            Category::Generic => 1,
+            Category::SysExit => 2,
+            Category::Shell => 3,
+            Category::Signal => 4,
         }
     }
+}
+
+/// Converts the error number of a failed system call, as returned by the
+/// `errno` crate’s `errno()` function, to an exit code through
+/// [`from_errno`].  Only available with the `errno` feature.
+///
+/// [`from_errno`]: fn.from_errno.html
+#[cfg(feature = "errno")]
+impl From<errno::Errno> for Code {
+    fn from(err: errno::Errno) -> Self {
+        from_errno(err.0)
+    }
+}
+
+/// Serializes the exit code as its [`name`], such as `"EX_IOERR"`, rather
+/// than as its number, so that stored data stays legible.  Only available
+/// with the `serde` feature.
+///
+/// [`name`]: enum.Code.html#method.name
+#[cfg(feature = "serde")]
+impl serde::Serialize for Code {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+/// Deserializes an exit code from any string accepted by `FromStr`.  An
+/// unrecognised string is a deserialization error.  Only available with the
+/// `serde` feature.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Code {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CodeVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for CodeVisitor {
+            type Value = Code;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an exit code name such as \"EX_IOERR\"")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Code, E> {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(CodeVisitor)
+    }
+}
+
+/// Reports the exit code as the exit status of the program, so that `Code`
+/// can be returned from `main`:
+///
+/// ```no_run
+/// use sysexit::Code;
+///
+/// fn main() -> Code {
+///     if std::env::args().count() > 2 {
+///         return Code::Usage;
+///     }
+///     Code::Success
+/// }
+/// ```
+#[cfg(feature = "std")]
+impl process::Termination for Code {
+    fn report(self) -> process::ExitCode {
+        process::ExitCode::from(self as i32 as u8)
+    }
+}
+
+/// Types that can be mapped to an exit code, such as errors that should
+/// cause the program to terminate.
+pub trait AsExitCode {
+    /// Returns the exit code the program should terminate with.
+    fn as_exit_code(&self) -> Code;
+}
+
+impl AsExitCode for Code {
+    fn as_exit_code(&self) -> Code {
+        *self
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsExitCode for io::ErrorKind {
+    fn as_exit_code(&self) -> Code {
+        Code::from(*self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsExitCode for io::Error {
+    fn as_exit_code(&self) -> Code {
+        Code::from(self.kind())
+    }
+}
+
+/// Provides a user-friendly explanation of the exit code.
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.reason(), *self as i32)
+    }
+}
+
+/// Parses an exit code from its [`name`], such as `"EX_USAGE"` for the
+/// [sysexits(3)] codes or `"SIGTERM"` for signals, or from the user-friendly
+/// explanation produced by the `Display` implementation, such as
+/// `"i/o error (74)"`.  Names are matched case-insensitively.
+///
+/// ```
+/// use sysexit::{self, Code};
+///
+/// assert_eq!("EX_DATAERR".parse(), Ok(sysexit::DataErr));
+/// assert_eq!("sigterm".parse(), Ok(sysexit::SIGTERM));
+/// assert_eq!("i/o error (74)".parse(), Ok(sysexit::IoErr));
+/// assert!("EX_NONSENSE".parse::<Code>().is_err());
+/// ```
+///
+/// [`name`]: enum.Code.html#method.name
+/// [sysexits(3)]: https://man.openbsd.org/sysexits.3
+#[cfg(feature = "std")]
+impl FromStr for Code {
+    type Err = ParseCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseCodeError {
+            input: s.to_string(),
+        };
+        if let Some(&code) = ALL.iter().find(|code| code.name().eq_ignore_ascii_case(s)) {
+            return Ok(code);
+        }
+
+        if !s.ends_with(')') {
+            return Err(err());
+        }
+        let open = s.rfind(" (").ok_or_else(err)?;
+        let reason = &s[..open];
+        let n: i32 = s[open + 2..s.len() - 1].parse().map_err(|_| err())?;
+        ALL.iter()
+            .cloned()
+            .find(|&code| code as i32 == n && code.reason() == reason)
+            .ok_or_else(err)
+    }
+}
+
+/// An error returned when parsing an exit code from a string fails.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseCodeError {
+    input: String,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ParseCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unrecognised exit code: {:?}", self.input)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for ParseCodeError {}
+
+/// An error returned by [`try_from_i32`] and [`try_from_i32_strict`] when
+/// an integer is not an acceptable exit code.
+///
+/// [`try_from_i32`]: fn.try_from_i32.html
+/// [`try_from_i32_strict`]: fn.try_from_i32_strict.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TryFromCodeError {
+    /// The integer is outside the valid 0–255 (inclusive) range.
+    OutOfRange(i32),
+
+    /// The integer is in the valid range, but is not an exit code recognised
+    /// on the current platform.  Only returned by [`try_from_i32_strict`].
+    ///
+    /// [`try_from_i32_strict`]: fn.try_from_i32_strict.html
+    Unrecognised(i32),
+}
+
+impl fmt::Display for TryFromCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TryFromCodeError::OutOfRange(n) => write!(f, "exit code out of range: {}", n),
+            TryFromCodeError::Unrecognised(n) => write!(f, "unrecognised exit code: {}", n),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for TryFromCodeError {}
+
+/// A diagnosis of why a process failed, combining its exit code with a
+/// human-readable explanation.
+///
+/// It implements [`std::error::Error`] so that it can be propagated with
+/// `?`, and [`AsExitCode`] so that it can finally be turned into the exit
+/// code of the program, for example with [`main_wrapper`].
+///
+/// [`std::error::Error`]: https://doc.rust-lang.org/std/error/trait.Error.html
+/// [`AsExitCode`]: trait.AsExitCode.html
+/// [`main_wrapper`]: fn.main_wrapper.html
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnosis {
+    /// The exit code of the process.
+    pub code: Code,
+
+    /// The number of the signal that terminated the process, if it was
+    /// terminated by a signal, or on Windows by an unhandled exception that
+    /// maps to one.
+    pub signal: Option<i32>,
+
+    /// An explanation of how the process terminated.
+    pub message: String,
+}
+
+#[cfg(feature = "std")]
+impl Diagnosis {
+    /// Diagnoses the provided [`std::process::ExitStatus`].
+    ///
+    /// ```
+    /// use std::process;
+    /// use sysexit::Diagnosis;
+    ///
+    /// let exit_status = process::Command::new("false")
+    ///     .status()
+    ///     .expect("failed to run false(1)");
+    /// let diagnosis = Diagnosis::from_status(exit_status);
+    /// assert_eq!(diagnosis.to_string(), "failure (1): exited with status 1");
+    /// ```
+    ///
+    /// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
+    pub fn from_status(status: process::ExitStatus) -> Diagnosis {
+        let code = Code::from(status);
+        let signal = platform_signal(status);
+        let by_signal = |signal| match code.signal_name() {
+            Some(name) => format!("signal {} ({})", signal, name),
+            None => format!("signal {}", signal),
+        };
+        let message = match (signal, status.code()) {
+            (Some(signal), None) => format!("terminated by {}", by_signal(signal)),
+            // an unhandled exception on Windows, reported as the closest signal
+            (Some(signal), Some(n)) => format!(
+                "terminated by exception {:#X}, as if by {}",
+                n as u32,
+                by_signal(signal)
+            ),
+            (None, Some(n)) if cfg!(windows) && ntstatus_code(n).is_some() => {
+                format!("terminated by exception {:#X}", n as u32)
+            }
+            (None, Some(n)) => format!("exited with status {}", n),
+            (None, None) => String::from("terminated for an unknown reason"),
+        };
+
+        Diagnosis {
+            code,
+            signal,
+            message,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Diagnosis {}
+
+#[cfg(feature = "std")]
+impl AsExitCode for Diagnosis {
+    fn as_exit_code(&self) -> Code {
+        self.code
+    }
+}
+
+struct UnknownCode(i32);
+
+impl fmt::Display for UnknownCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown exit code ({})", self.0)
+    }
+}
+
+/// A fatal signal _N_ that terminated a process, as reported by the exit code
+/// 128 + _N_.
+///
+/// `Code` only has codes for the common signals, and converts the exit code
+/// of any other signal to [`sysexit::Unknown`].  `Signal` covers every signal
+/// number from 1 to 64, so that every exit code in the signal range 129–192
+/// round-trips, whether or not the signal has a code of its own:
+///
+/// ```
+/// use sysexit::{self, Signal};
+///
+/// let signal = Signal::from_exit_code(162).unwrap();
+/// assert_eq!(signal.number(), 34);
+/// assert_eq!(signal.to_i32(), 162);
+/// assert_eq!(signal.code(), None);
+///
+/// let signal = Signal::from_exit_code(143).unwrap();
+/// assert_eq!(signal.code(), Some(sysexit::SIGTERM));
+/// ```
+///
+/// [`sysexit::Unknown`]: enum.Code.html#variant.Unknown
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Signal(i32);
+
+impl Signal {
+    /// Returns the signal reported by the exit code `n`, or `None` if `n` is
+    /// outside the signal range 129–192.
+    pub const fn from_exit_code(n: i32) -> Option<Signal> {
+        if SIGBASE < n && n <= SIGBASE + SIGMAX {
+            Some(Signal(n - SIGBASE))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the signal that terminated the process of the provided
+    /// [`std::process::ExitStatus`], or `None` if it exited normally with an
+    /// exit code outside the signal range.
+    ///
+    /// Like [`from_status`], this uses the signal decoded by the operating
+    /// system on Unix, and otherwise the 128 + _N_ exit code reported by a
+    /// shell.
+    ///
+    /// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
+    /// [`from_status`]: fn.from_status.html
+    #[cfg(feature = "std")]
+    pub fn from_status(status: process::ExitStatus) -> Option<Signal> {
+        platform_exit_code(status).and_then(Signal::from_exit_code)
+    }
+
+    /// Returns the signal number _N_.
+    pub const fn number(self) -> i32 {
+        self.0
+    }
+
+    /// Returns the exit code 128 + _N_ reporting the signal.
+    pub const fn to_i32(self) -> i32 {
+        SIGBASE + self.0
+    }
+
+    /// Returns the code of the signal, such as [`sysexit::SIGTERM`], if it
+    /// has a code of its own on the current platform.
+    ///
+    /// [`sysexit::SIGTERM`]: enum.Code.html#variant.SIGTERM
+    pub fn code(self) -> Option<Code> {
+        let code = Code::from(self.to_i32());
+        if code as i32 == self.to_i32() {
+            Some(code)
+        } else {
+            None
+        }
+    }
+}
+
+/// Converts a signal to the exit code 128 + _N_ reporting it.
+impl From<Signal> for i32 {
+    fn from(signal: Signal) -> Self {
+        signal.to_i32()
+    }
+}
+
+/// Provides a user-friendly explanation of the signal, which is that of its
+/// code if it has one:
+///
+/// ```
+/// use sysexit::Signal;
+///
+/// assert_eq!(Signal::from_exit_code(143).unwrap().to_string(), "termination signal (143)");
+/// assert_eq!(Signal::from_exit_code(162).unwrap().to_string(), "signal 34 (162)");
+/// ```
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.code() {
+            Some(code) => fmt::Display::fmt(&code, f),
+            None => write!(f, "signal {} ({})", self.0, self.to_i32()),
+        }
+    }
+}
+
+/// The band of exit codes an exit code falls into, as returned by
+/// [`Code::category`].
+///
+/// [`Code::category`]: enum.Code.html#method.category
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Category {
+    /// Successful termination (0).
+    Success,
+
+    /// Generic failure, not further specified (1–2).
+    Generic,
+
+    /// An exit code from [sysexits(3)] (64–78).
+    ///
+    /// [sysexits(3)]: https://man.openbsd.org/sysexits.3
+    SysExit,
+
+    /// An exit code reserved by shells for failures to run a command, or for
+    /// termination by an unknown signal (126–128).
+    Shell,
+
+    /// Termination by a fatal signal (129–192).
+    Signal,
+}
+
+/// Renders the category as a lower-case word, such as `"signal"`.
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Category::Success => "success",
+            Category::Generic => "generic",
+            Category::SysExit => "system",
+            Category::Shell => "shell",
+            Category::Signal => "signal",
+        })
+    }
+}
+
+/// Conventions of particular programs for the meaning of their exit codes,
+/// used with [`classify_with_profile`].
+///
+/// [`classify_with_profile`]: fn.classify_with_profile.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Profile {
+    /// The conventions of [sysexits(3)] and shells, as described in the
+    /// module documentation.
+    ///
+    /// [sysexits(3)]: https://man.openbsd.org/sysexits.3
+    Default,
+
+    /// The conventions of [grep(1)], which exits with 0 if a line is
+    /// selected, 1 if no lines were selected, and 2 (or greater) if an error
+    /// occurred.  Not finding a match is therefore not an error.
+    ///
+    /// [grep(1)]: https://man.openbsd.org/grep.1
+    Grep,
+
+    /// The conventions of [cargo(1)], which exits with 101 if `cargo test`
+    /// ran but some tests failed, and 1 for other errors, such as an invalid
+    /// manifest or an unknown subcommand.  Since 101 is also the exit code
+    /// of a Rust program that panics, it is only interpreted as failed tests
+    /// under this profile.
+    ///
+    /// [cargo(1)]: https://doc.rust-lang.org/cargo/commands/cargo.html
+    Cargo,
+
+    /// The conventions of a particular shell for the exit codes 126 and 127,
+    /// which report failures to run a command.  See [`ShellKind`] for the
+    /// shells.
+    ///
+    /// [`ShellKind`]: enum.ShellKind.html
+    Shell(ShellKind),
+}
+
+/// Shells whose exit codes 126 and 127 can be interpreted, used with
+/// [`Profile::Shell`].
+///
+/// The shells agree on these codes.  They exit with 127 when the command is
+/// not found, whether it was looked up in `PATH` or given as a path that does
+/// not exist, and with 126 when it is found but cannot be executed, such as a
+/// file without the execute permission, a directory, or a binary in a format
+/// the system does not support.  No shell-specific difference is known, so
+/// every shell is interpreted alike.
+///
+/// [`Profile::Shell`]: enum.Profile.html#variant.Shell
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShellKind {
+    /// [bash(1)].
+    ///
+    /// [bash(1)]: https://linux.die.net/man/1/bash
+    Bash,
+
+    /// [dash(1)], commonly installed as _/bin/sh_.
+    ///
+    /// [dash(1)]: https://man.archlinux.org/man/dash.1
+    Dash,
+
+    /// [zsh(1)].
+    ///
+    /// [zsh(1)]: https://zsh.sourceforge.io/Doc/Release/Invocation.html
+    Zsh,
+}
+
+/// The interpretation of an exit code under a [`Profile`].
+///
+/// [`Profile`]: enum.Profile.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Classification {
+    /// The exit code as it was reported by the process.
+    pub raw: i32,
+
+    /// The closest generic exit code.
+    pub code: Code,
+
+    /// Whether the exit code indicates failure under the profile.
+    pub is_error: bool,
+
+    /// A short explanation of the exit code under the profile.
+    pub reason: &'static str,
+}
+
+/// Provides a user-friendly explanation of the classified exit code.
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.reason, self.raw)
+    }
+}
+
+/// The outcome of a [grep(1)] search, as determined by [`grep_outcome`].
+///
+/// [grep(1)]: https://man.openbsd.org/grep.1
+/// [`grep_outcome`]: fn.grep_outcome.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GrepOutcome {
+    /// One or more lines were selected (0).
+    Matched,
+
+    /// No lines were selected (1).
+    NoMatch,
+
+    /// An error occurred (2 or greater).
+    Error,
+}
+
+#[cfg(all(feature = "std", target_family = "unix"))]
+fn platform_exit_code(status: process::ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status
+        .code()
+        .or_else(|| status.signal().map(|signal| SIGBASE + signal))
+}
+
+#[cfg(all(feature = "std", windows))]
+fn platform_exit_code(status: process::ExitStatus) -> Option<i32> {
+    status
+        .code()
+        .map(|n| ntstatus_code(n).map_or(n, |code| code as i32))
+}
+
+#[cfg(all(feature = "std", not(any(target_family = "unix", windows))))]
+fn platform_exit_code(status: process::ExitStatus) -> Option<i32> {
+    status.code()
+}
+
+/// Maps the exit code of a Windows process terminated by an unhandled
+/// exception, which is the NTSTATUS value of the exception, to the exit code
+/// of the closest Unix signal, or of the failure it reports.
+///
+/// NTSTATUS values are `u32`s with the high bit set for errors, so
+/// `ExitStatus::code()` reports them as negative `i32`s, and they are
+/// reinterpreted here rather than converted.
+#[cfg(any(feature = "std", test))]
+fn ntstatus_code(n: i32) -> Option<Code> {
+    match n as u32 {
+        // STATUS_BREAKPOINT
+        0x8000_0003 => Some(SIGTRAP),
+        // STATUS_ACCESS_VIOLATION
+        0xC000_0005 => Some(SIGSEGV),
+        // STATUS_NO_MEMORY
+        0xC000_0017 => Some(OsErr),
+        // STATUS_ILLEGAL_INSTRUCTION
+        0xC000_001D => Some(SIGILL),
+        // STATUS_INTEGER_DIVIDE_BY_ZERO
+        0xC000_0094 => Some(SIGFPE),
+        // STATUS_STACK_OVERFLOW
+        0xC000_00FD => Some(SIGSEGV),
+        // STATUS_CONTROL_C_EXIT
+        0xC000_013A => Some(SIGINT),
+        // STATUS_STACK_BUFFER_OVERRUN, raised by `process::abort`
+        0xC000_0409 => Some(SIGABRT),
+        _ => None,
+    }
+}
+
+#[cfg(all(feature = "std", target_family = "unix"))]
+fn platform_signal(status: process::ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(all(feature = "std", target_family = "unix"))]
+fn platform_stopped_signal(status: process::ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.stopped_signal()
+}
+
+#[cfg(all(feature = "std", not(target_family = "unix")))]
+fn platform_stopped_signal(_: process::ExitStatus) -> Option<i32> {
+    None
+}
+
+/// Windows has no signals, but the unhandled exceptions that [`ntstatus_code`]
+/// maps to a signal are reported as that signal.
+///
+/// [`ntstatus_code`]: fn.ntstatus_code.html
+#[cfg(all(feature = "std", windows))]
+fn platform_signal(status: process::ExitStatus) -> Option<i32> {
+    status
+        .code()
+        .and_then(ntstatus_code)
+        .and_then(Code::signal_number)
+}
+
+#[cfg(all(feature = "std", not(any(target_family = "unix", windows))))]
+fn platform_signal(_: process::ExitStatus) -> Option<i32> {
+    None
+}
+
+pub use self::Code::*;
+
+/// Converts [`std::process::ExitStatus`] to [`sysexit::Code`].
+///
+/// On Unix, if the process was terminated by a fatal signal, the corresponding
+/// signal exit code is returned.  On Windows, if the process was terminated by
+/// an unhandled exception, such as an access violation, the exit code of the
+/// closest signal or failure is returned.  If the passed exit status cannot be
+/// determined, [`sysexit::Unknown`] (2) is returned.
+///
+/// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
+/// [`sysexit::Code`]: enum.Code.html
+/// [`sysexit::Unknown`]: enum.Code.html#variant.Unknown
+#[cfg(feature = "std")]
+pub fn from_status(status: process::ExitStatus) -> Code {
+    Code::from(status)
+}
+
+/// Converts [`std::process::ExitStatus`] to [`sysexit::Code`], preferring
+/// the decoded signal number over the 128 + _N_ heuristic.
+///
+/// If the process was terminated by a signal, the code of the signal number
+/// decoded from the status (`WTERMSIG`) is returned, whatever the raw status
+/// looks like.  Otherwise the exit code is converted like [`from_status`], so
+/// that a shell’s 128 + _N_ report of a signal that terminated its own child
+/// is still recognised.
+///
+/// On Windows the signal is the one closest to an unhandled exception, as in
+/// [`from_status`].  Elsewhere signals are not decoded and this behaves like
+/// [`from_status`].
+///
+/// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
+/// [`sysexit::Code`]: enum.Code.html
+/// [`from_status`]: fn.from_status.html
+#[cfg(feature = "std")]
+pub fn prefer_real_signal(status: process::ExitStatus) -> Code {
+    match platform_signal(status) {
+        Some(signal) => Code::from(SIGBASE + signal),
+        None => Code::from(status),
+    }
+}
+
+/// Converts [`std::process::ExitStatus`] to [`sysexit::Code`] like
+/// [`from_status`], but collapses every signal termination into the single
+/// [`sysexit::Signaled`] (128) code.
+///
+/// This is useful when it only matters that the process was terminated by a
+/// fatal signal, and not which one.
+///
+/// Example:
+///
+/// ```
+/// use std::process;
+/// use sysexit;
+///
+/// let exit_status = process::Command::new("sh")
+///     .arg("-c").arg("kill -TERM $$")
+///     .status()
+///     .expect("failed to run sh(1)");
+/// assert_eq!(sysexit::from_status_coarse(exit_status), sysexit::Signaled);
+/// ```
+///
+/// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
+/// [`sysexit::Code`]: enum.Code.html
+/// [`sysexit::Signaled`]: enum.Code.html#variant.Signaled
+/// [`from_status`]: fn.from_status.html
+#[cfg(feature = "std")]
+pub fn from_status_coarse(status: process::ExitStatus) -> Code {
+    match Signal::from_status(status) {
+        Some(_) => Signaled,
+        None => Code::from(status),
+    }
+}
+
+/// Provides a user-friendly explanation of an exit code that is not
+/// recognised, preserving the original number rather than the
+/// [`sysexit::Unknown`] (2) it is classified as:
+///
+/// ```
+/// use sysexit;
+///
+/// assert_eq!(sysexit::unknown_with_raw(42).to_string(), "unknown exit code (42)");
+/// ```
+///
+/// [`sysexit::Unknown`]: enum.Code.html#variant.Unknown
+pub fn unknown_with_raw(n: i32) -> impl fmt::Display {
+    UnknownCode(n)
+}
+
+/// Runs `f` and returns the exit code the program should terminate with.
+///
+/// If `f` fails, the error is printed to stderr and mapped to an exit code
+/// through [`AsExitCode`].  The process is not exited, so the returned code
+/// can be passed on to [`std::process::exit`] or returned from `main`.
+///
+/// Example:
+///
+/// ```
+/// use std::io;
+/// use sysexit;
+///
+/// let code = sysexit::main_wrapper(|| {
+///     Err(io::Error::new(io::ErrorKind::PermissionDenied, "cannot open file"))
+/// });
+/// assert_eq!(code, sysexit::NoPerm);
+/// ```
+///
+/// [`AsExitCode`]: trait.AsExitCode.html
+/// [`std::process::exit`]: https://doc.rust-lang.org/std/process/fn.exit.html
+#[cfg(feature = "std")]
+pub fn main_wrapper<E, F>(f: F) -> Code
+where
+    E: AsExitCode + fmt::Display,
+    F: FnOnce() -> Result<(), E>,
+{
+    report(f, &mut io::stderr())
+}
+
+#[cfg(feature = "std")]
+fn report<E, F, W>(f: F, w: &mut W) -> Code
+where
+    E: AsExitCode + fmt::Display,
+    F: FnOnce() -> Result<(), E>,
+    W: Write,
+{
+    match f() {
+        Ok(()) => Success,
+        Err(err) => {
+            let _ = writeln!(w, "error: {}", err);
+            err.as_exit_code()
+        }
+    }
+}
+
+/// Installs a panic hook that terminates the process with
+/// [`sysexit::Software`] (70) after a panic, rather than with the default
+/// status of 101.  The hook first runs the previously installed hook, so the
+/// panic message is still printed.
+///
+/// The process exits from the panicking thread, so panics are never caught
+/// or unwound past once the hook is installed.
+///
+/// ```no_run
+/// use sysexit;
+///
+/// sysexit::install_panic_hook();
+/// panic!("exits with 70");
+/// ```
+///
+/// [`sysexit::Software`]: enum.Code.html#variant.Software
+#[cfg(feature = "std")]
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        previous(info);
+        Software.exit();
+    }));
+}
+
+/// Lists all the exit codes recognised on the current platform, in the
+/// order they are declared.
+pub fn all() -> &'static [Code] {
+    ALL
+}
+
+/// Lists the numbers of all the exit codes recognised on the current
+/// platform in ascending order, for example to generate equivalent tables in
+/// other languages.
+pub fn discriminants() -> &'static [i32] {
+    &DISCRIMINANTS
+}
+
+/// Renders a Markdown table of all the exit codes, one row per code as
+/// produced by [`Code::to_markdown_row`], preceded by a header.
+///
+/// [`Code::to_markdown_row`]: enum.Code.html#method.to_markdown_row
+#[cfg(feature = "std")]
+pub fn markdown_table() -> String {
+    let mut table = String::from("| Code | Name | Category | Description |\n|---|---|---|---|\n");
+    for code in ALL {
+        table.push_str(&code.to_markdown_row());
+        table.push('\n');
+    }
+    table
+}
+
+/// Interprets the exit code `n` according to the conventions of the program
+/// described by `profile`.
+///
+/// Example:
+///
+/// ```
+/// use sysexit::{self, Profile};
+///
+/// let no_match = sysexit::classify_with_profile(1, Profile::Grep);
+/// assert!(!no_match.is_error);
+/// assert_eq!(no_match.to_string(), "no match (1)");
+///
+/// let failure = sysexit::classify_with_profile(1, Profile::Default);
+/// assert!(failure.is_error);
+/// ```
+pub fn classify_with_profile(n: i32, profile: Profile) -> Classification {
+    let code = Code::from(n);
+    let (code, is_error, reason) = match (profile, n) {
+        (Profile::Grep, 0) => (code, false, "matched"),
+        (Profile::Grep, 1) => (code, false, "no match"),
+        (Profile::Grep, 2) => (code, true, "error"),
+        (Profile::Cargo, 1) => (code, true, "error"),
+        (Profile::Cargo, 101) => (Failure, true, "tests failed"),
+        (Profile::Shell(_), 126) => (code, true, "command not executable"),
+        (Profile::Shell(_), 127) => (code, true, "command not found"),
+        _ => (code, code != Success, code.reason()),
+    };
+
+    Classification {
+        raw: n,
+        code,
+        is_error,
+        reason,
+    }
+}
+
+/// Determines the outcome of a [grep(1)] search from its exit code.
+///
+/// By convention grep exits with 0 if one or more lines were selected, 1 if
+/// no lines were selected, and 2 if an error occurred.  Any other non-zero
+/// exit code, such as from a fatal signal, is also considered an error.
+///
+/// [grep(1)]: https://man.openbsd.org/grep.1
+pub fn grep_outcome(n: i32) -> GrepOutcome {
+    match n {
+        0 => GrepOutcome::Matched,
+        1 => GrepOutcome::NoMatch,
+        _ => GrepOutcome::Error,
+    }
+}
+
+/// Converts the error number of a failed system call to an exit code by way
+/// of its [`std::io::ErrorKind`].
+///
+/// On Unix the number is an `errno` value, such as `EACCES`.  On Windows it
+/// is a `GetLastError` code, such as `ERROR_ACCESS_DENIED` (5), which is also
+/// what `io::Error::raw_os_error` and the `errno` crate return there.
+///
+/// ```
+/// use std::io;
+/// use sysexit;
+///
+/// let errno = io::Error::last_os_error().raw_os_error().unwrap_or(0);
+/// println!("{}", sysexit::from_errno(errno));
+/// ```
+///
+/// [`std::io::ErrorKind`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html
+#[cfg(feature = "std")]
+pub fn from_errno(n: i32) -> Code {
+    Code::from(io::Error::from_raw_os_error(n).kind())
+}
+
+/// Converts [`std::io::ErrorKind`] to [`sysexit::Code`].  This is the same as
+/// the `From<io::ErrorKind>` conversion, and [`error_kinds_for`] is its
+/// inverse.
+///
+/// [`std::io::ErrorKind`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html
+/// [`sysexit::Code`]: enum.Code.html
+/// [`error_kinds_for`]: fn.error_kinds_for.html
+#[cfg(feature = "std")]
+pub fn codes_for_error_kind(kind: io::ErrorKind) -> Code {
+    Code::from(kind)
+}
+
+/// Lists the kinds of [`std::io::ErrorKind`] that convert to the exit code
+/// `code`, since several kinds of errors map to the same exit code.
+///
+/// [`sysexit::IoErr`] is used for any kind of error that has no more
+/// specific exit code, so only the most common of those are listed for it.
+/// For exit codes that no kind of error maps to, the list is empty.
+///
+/// Example:
+///
+/// ```
+/// use std::io;
+/// use sysexit;
+///
+/// assert_eq!(
+///     sysexit::error_kinds_for(sysexit::DataErr),
+///     &[io::ErrorKind::InvalidInput, io::ErrorKind::InvalidData]
+/// );
+/// ```
+///
+/// [`std::io::ErrorKind`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html
+/// [`sysexit::IoErr`]: enum.Code.html#variant.IoErr
+#[cfg(feature = "std")]
+pub fn error_kinds_for(code: Code) -> &'static [io::ErrorKind] {
+    use io::ErrorKind::*;
+    match code {
+        OsFile => &[NotFound],
+        NoPerm => &[PermissionDenied],
+        Unavailable => &[AddrInUse, AddrNotAvailable],
+        Protocol => &[
+            ConnectionRefused,
+            ConnectionReset,
+            ConnectionAborted,
+            NotConnected,
+            BrokenPipe,
+        ],
+        CantCreat => &[AlreadyExists],
+        DataErr => &[InvalidInput, InvalidData],
+        IoErr => &[
+            Interrupted,
+            TimedOut,
+            WouldBlock,
+            WriteZero,
+            UnexpectedEof,
+            Other,
+        ],
+        _ => &[],
+    }
+}
+
+/// Returns the exit code a wrapper program should exit with to faithfully
+/// propagate the outcome of a child process that ended with `child`.
+///
+/// The policy is as follows:
+///
+/// * If the child exited normally with a code in the valid 0–255 range, the
+///   wrapper exits with the same code, even if it is not recognised.
+///
+/// * If the child was terminated by signal _N_, the wrapper exits with the
+///   128 + _N_ code a shell would have reported, rather than being terminated
+///   by the signal itself.  On Windows an unhandled exception is reported as
+///   the closest signal or failure, as by [`from_status`].
+///
+/// * If the child was stopped by signal _N_, the wrapper also exits with
+///   128 + _N_, as bash reports for a stopped job.
+///
+/// * Otherwise the outcome cannot be propagated, such as for an exit code
+///   outside the valid range on Windows or a continued child, and the wrapper
+///   exits with the catch-all code 2 of [`sysexit::Unknown`].
+///
+/// [`from_status`]: fn.from_status.html
+/// [`sysexit::Unknown`]: enum.Code.html#variant.Unknown
+#[cfg(feature = "std")]
+pub fn wrapper_exit_code(child: process::ExitStatus) -> i32 {
+    let stopped = || platform_stopped_signal(child).map(|signal| SIGBASE + signal);
+    match platform_exit_code(child).or_else(stopped) {
+        Some(n) if is_valid(n) => n,
+        _ => Unknown as i32,
+    }
+}
+
+/// Infers the exit code from an error message printed by a shell, such as
+/// `bash: foo: command not found`.  Returns `None` if the message is not
+/// recognised.
+///
+/// The message is matched case-insensitively against these phrases:
+///
+/// | Phrase                         | Code                  |
+/// |--------------------------------|-----------------------|
+/// | `command not found`            | `NotFound` (127)      |
+/// | `: not found` (dash)           | `NotFound` (127)      |
+/// | `Permission denied`            | `NotExecutable` (126) |
+/// | `cannot execute`               | `NotExecutable` (126) |
+/// | `No such file or directory`    | `OsFile` (72)         |
+///
+/// Example:
+///
+/// ```
+/// use sysexit;
+///
+/// let code = sysexit::infer_from_shell_message("bash: foo: command not found");
+/// assert_eq!(code, Some(sysexit::NotFound));
+/// ```
+#[cfg(feature = "std")]
+pub fn infer_from_shell_message(msg: &str) -> Option<Code> {
+    let msg = msg.to_lowercase();
+    if msg.contains("command not found") || msg.contains(": not found") {
+        Some(NotFound)
+    } else if msg.contains("permission denied") || msg.contains("cannot execute") {
+        Some(NotExecutable)
+    } else if msg.contains("no such file or directory") {
+        Some(OsFile)
+    } else {
+        None
+    }
+}
+
+/// Returns the exit code of the provided [`std::process::ExitStatus`] if it
+/// is outside the valid 0–255 (inclusive) range, which means the process
+/// passed an invalid value to [exit(3)].
+///
+/// On Unix the exit code is truncated to its lowest 8 bits by the operating
+/// system, so that `exit(300)` is reported as 44, and this always returns
+/// `None`.  On Windows the full 32-bit exit code is preserved, so oversized
+/// and negative exit codes can be detected.  The NTSTATUS values of the
+/// unhandled exceptions recognised by [`from_status`], such as an access
+/// violation, are crashes rather than values passed to exit(3), and are not
+/// reported.
+///
+/// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
+/// [exit(3)]: https://man.openbsd.org/exit.3
+/// [`from_status`]: fn.from_status.html
+#[cfg(feature = "std")]
+pub fn had_oversized_code(status: process::ExitStatus) -> Option<i32> {
+    let is_crash = |n| cfg!(windows) && ntstatus_code(n).is_some();
+    status.code().filter(|&n| !is_valid(n) && !is_crash(n))
+}
+
+/// Converts an `i32` to an exit code, failing with
+/// [`TryFromCodeError::OutOfRange`] if it is outside the valid 0–255
+/// (inclusive) range, as tested by [`is_valid`].
+///
+/// The `From<i32>` conversion is lossy and silently classifies every value
+/// it does not recognise, including out-of-range ones such as -1 or 300, as
+/// [`sysexit::Unknown`].  This conversion instead reports values that can
+/// never be an exit status, while still classifying unassigned values in
+/// range, such as 50, as `Unknown`.  Use [`try_from_i32_strict`] to reject
+/// those as well.
+///
+/// `Code` cannot implement `TryFrom<i32>` itself, since the standard library
+/// derives it from `From<i32>`, which would then never fail.
+///
+/// ```
+/// use sysexit::{self, TryFromCodeError};
+///
+/// assert_eq!(sysexit::try_from_i32(74), Ok(sysexit::IoErr));
+/// assert_eq!(sysexit::try_from_i32(50), Ok(sysexit::Unknown));
+/// assert_eq!(sysexit::try_from_i32(256), Err(TryFromCodeError::OutOfRange(256)));
+/// ```
+///
+/// [`TryFromCodeError::OutOfRange`]: enum.TryFromCodeError.html#variant.OutOfRange
+/// [`is_valid`]: fn.is_valid.html
+/// [`sysexit::Unknown`]: enum.Code.html#variant.Unknown
+/// [`try_from_i32_strict`]: fn.try_from_i32_strict.html
+pub fn try_from_i32(n: i32) -> Result<Code, TryFromCodeError> {
+    if is_valid(n) {
+        Ok(Code::from(n))
+    } else {
+        Err(TryFromCodeError::OutOfRange(n))
+    }
+}
+
+/// Converts an `i32` to an exit code like [`try_from_i32`], but also fails
+/// with [`TryFromCodeError::Unrecognised`] if the value is in range but is
+/// not recognised on the current platform, as tested by [`is_recognised`].
+///
+/// ```
+/// use sysexit::{self, TryFromCodeError};
+///
+/// assert_eq!(sysexit::try_from_i32_strict(74), Ok(sysexit::IoErr));
+/// assert_eq!(sysexit::try_from_i32_strict(50), Err(TryFromCodeError::Unrecognised(50)));
+/// assert_eq!(sysexit::try_from_i32_strict(-1), Err(TryFromCodeError::OutOfRange(-1)));
+/// ```
+///
+/// [`try_from_i32`]: fn.try_from_i32.html
+/// [`TryFromCodeError::Unrecognised`]: enum.TryFromCodeError.html#variant.Unrecognised
+/// [`is_recognised`]: fn.is_recognised.html
+pub fn try_from_i32_strict(n: i32) -> Result<Code, TryFromCodeError> {
+    match try_from_i32(n)? {
+        code if code as i32 == n => Ok(code),
+        _ => Err(TryFromCodeError::Unrecognised(n)),
+    }
+}
+
+/// Converts a wide integer, such as one received over FFI, to an exit code.
+///
+/// Values outside the valid 0–255 (inclusive) range are classified as
+/// [`sysexit::Unknown`] (2), rather than being truncated to a valid but
+/// unrelated exit code.
+///
+/// [`sysexit::Unknown`]: enum.Code.html#variant.Unknown
+pub fn from_wide(n: i128) -> Code {
+    if (0..=255).contains(&n) {
+        Code::from(n as i32)
+    } else {
+        Unknown
+    }
+}
+
+/// Summarises the exit codes of a batch of work, returning the exit code for
+/// the batch as a whole and a one-line summary for the user.
+///
+/// The exit code for the batch is the most severe of `codes`, where failures
+/// are ranked from least to most severe as generic failures, [sysexits(3)]
+/// codes, shell codes, and signals.  Among exit codes of equal severity, the
+/// first is used.  An empty batch is successful.
+///
+/// The summary counts each distinct exit code in the order they first
+/// occur:
+///
+/// ```
+/// use sysexit;
+///
+/// let (code, summary) = sysexit::summarize(&[
+///     sysexit::Success,
+///     sysexit::IoErr,
+///     sysexit::Success,
+///     sysexit::SIGTERM,
+///     sysexit::Success,
+/// ]);
+/// assert_eq!(code, sysexit::SIGTERM);
+/// assert_eq!(summary, "3 succeeded, 1 i/o error, 1 killed by SIGTERM");
+/// ```
+///
+/// [sysexits(3)]: https://man.openbsd.org/sysexits.3
+#[cfg(feature = "std")]
+pub fn summarize(codes: &[Code]) -> (Code, String) {
+    let mut worst = Success;
+    let mut counts: Vec<(Code, usize)> = Vec::new();
+    for &code in codes {
+        if code.severity() > worst.severity() {
+            worst = code;
+        }
+        match counts.iter_mut().find(|entry| entry.0 == code) {
+            Some(entry) => entry.1 += 1,
+            None => counts.push((code, 1)),
+        }
+    }
+
+    if counts.is_empty() {
+        return (worst, String::from("nothing to summarize"));
+    }
+    let summary = counts
+        .iter()
+        .map(|&(code, n)| match (code, code.signal_name()) {
+            (Success, _) => format!("{} succeeded", n),
+            (_, Some(name)) => format!("{} killed by {}", n, name),
+            (_, None) => format!("{} {}", n, code.reason()),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    (worst, summary)
+}
+
+/// Computes the exit code of an idempotent tool, such as a configuration
+/// management tool, that distinguishes between making no changes, making
+/// changes, and failing.
+///
+/// If `error` is present its number is returned.  Otherwise the exit code is
+/// [`sysexit::Success`] (0) if nothing `changed`, and 2 if something did,
+/// following the convention of `puppet agent --detailed-exitcodes` and
+/// `terraform plan -detailed-exitcode`.
+///
+/// The number is returned rather than a [`sysexit::Code`], because 2 here
+/// reports a successful change, not the catch-all [`sysexit::Unknown`] code
+/// that shares its number.
+///
+/// Example:
+///
+/// ```
+/// use sysexit;
+///
+/// assert_eq!(sysexit::from_change_outcome(false, None), 0);
+/// assert_eq!(sysexit::from_change_outcome(true, None), 2);
+/// assert_eq!(sysexit::from_change_outcome(true, Some(sysexit::IoErr)), 74);
+/// ```
+///
+/// [`sysexit::Success`]: enum.Code.html#variant.Success
+/// [`sysexit::Code`]: enum.Code.html
+/// [`sysexit::Unknown`]: enum.Code.html#variant.Unknown
+pub fn from_change_outcome(changed: bool, error: Option<Code>) -> i32 {
+    match (changed, error) {
+        (_, Some(code)) => code as i32,
+        (true, None) => 2,
+        (false, None) => Success as i32,
+    }
+}
+
+/// Determines if the provided [`std::process::ExitStatus`] was successful.
+///
+/// Example:
+///
+/// ```
+/// use std::process;
+/// use sysexit;
+///
+/// let exit_status = process::Command::new("true")
+///     .status()
+///     .expect("failed to run true(1)");
+/// assert!(sysexit::is_success(exit_status));
+/// ```
+///
+/// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
+#[cfg(feature = "std")]
+pub fn is_success(status: process::ExitStatus) -> bool {
+    Code::from(status) == Success
+}
+
+/// Determines if the provided [`std::process::ExitStatus`] was successful,
+/// where in addition to [`sysexit::Success`] any of the exit codes in
+/// `extra_success` are considered successful.  This is useful for programs
+/// that use other exit codes for successful outcomes, such as “already up to
+/// date”.
+///
+/// The exit code is matched by its original number, so that codes without a
+/// [`sysexit::Code`] of their own, such as 3, can be listed.  A termination by
+/// signal _N_ is matched by the exit code 128 + _N_.
+///
+/// Example:
+///
+/// ```
+/// use std::process;
+/// use sysexit;
+///
+/// let exit_status = process::Command::new("sh")
+///     .args(["-c", "exit 3"])
+///     .status()
+///     .expect("failed to run sh(1)");
+/// assert!(sysexit::is_success_with(exit_status, &[3]));
+/// assert!(!sysexit::is_success_with(exit_status, &[4]));
+/// ```
+///
+/// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
+/// [`sysexit::Success`]: enum.Code.html#variant.Success
+/// [`sysexit::Code`]: enum.Code.html
+#[cfg(feature = "std")]
+pub fn is_success_with(status: process::ExitStatus, extra_success: &[i32]) -> bool {
+    match platform_exit_code(status) {
+        Some(n) => n == Success as i32 || extra_success.contains(&n),
+        None => false,
+    }
+}
+
+/// Determines if the provided [`std::process::ExitStatus`] was unsuccessful.
+///
+/// Example:
+///
+/// ```
+/// use std::process;
+/// use sysexit;
+///
+/// let exit_status = process::Command::new("false")
+///     .status()
+///     .expect("failed to run false(1)");
+/// assert!(sysexit::is_error(exit_status));
+/// ```
+///
+/// [`std::process::ExitStatus`]: https://doc.rust-lang.org/std/process/struct.ExitStatus.html
+#[cfg(feature = "std")]
+pub fn is_error(status: process::ExitStatus) -> bool {
+    !is_success(status)
+}
+
+/// Entry point for fuzzing the classifiers with [cargo-fuzz], only available
+/// with the `fuzz` feature.
+///
+/// The bytes in `data` are interpreted as exit codes, wide integers, raw
+/// wait statuses (on Unix), and strings, which are passed to the classifying
+/// functions.  It panics if any of them panic or return an exit code that is
+/// not one of [`all`].
+///
+/// [cargo-fuzz]: https://github.com/rust-fuzz/cargo-fuzz
+/// [`all`]: fn.all.html
+#[cfg(feature = "fuzz")]
+pub fn fuzz_classify(data: &[u8]) {
+    let check = |code: Code| assert!(ALL.contains(&code), "{:?}", code);
+
+    for chunk in data.chunks(4) {
+        let mut bytes = [0; 4];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        let n = i32::from_le_bytes(bytes);
+
+        check(Code::from(n));
+        check(Code::from(Some(n)));
+        check(from_wide(n as i128));
+        for &profile in &[
+            Profile::Default,
+            Profile::Grep,
+            Profile::Cargo,
+            Profile::Shell(ShellKind::Bash),
+            Profile::Shell(ShellKind::Dash),
+            Profile::Shell(ShellKind::Zsh),
+        ] {
+            check(classify_with_profile(n, profile).code);
+        }
+        let _ = is_reserved(n);
+        let _ = is_recognised(n);
+        let _ = unknown_with_raw(n).to_string();
+
+        #[cfg(target_family = "unix")]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            let status = process::ExitStatus::from_raw(n);
+            check(from_status(status));
+            check(prefer_real_signal(status));
+            check(from_status_coarse(status));
+            let _ = had_oversized_code(status);
+        }
+    }
+
+    for chunk in data.chunks(16) {
+        let mut bytes = [0; 16];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        check(from_wide(i128::from_le_bytes(bytes)));
+    }
+
+    let s = String::from_utf8_lossy(data);
+    if let Ok(code) = s.parse::<Code>() {
+        check(code);
+    }
+    if let Some(code) = infer_from_shell_message(&s) {
+        check(code);
+    }
+}
+
+/// Tests if the provided exit code is reserved, and has a special meaning in
+/// shells.
+///
+/// This tests for the conventional bands of exit codes: 0–2, 64–78, and
+/// 126–192, regardless of whether every code in a band is recognised on the
+/// current platform.  Use [`is_recognised`] to test for the precise set of
+/// exit codes.
+///
+/// [`is_recognised`]: fn.is_recognised.html
+pub fn is_reserved(n: i32) -> bool {
+    (Success as i32 <= n && n <= Unknown as i32) || (Usage as i32 <= n && n <= Config as i32)
+        || (NotExecutable as i32 <= n && n <= SIGBASE + SIGMAX)
+}
+
+/// Tests if the provided exit code is recognised on the current platform,
+/// that is if it converts to an exit code with the same number rather than
+/// to [`sysexit::Unknown`].
+///
+/// Unlike [`is_reserved`], this excludes the exit codes of signals without a
+/// code of their own, whose numbers vary between platforms.  Use [`Signal`]
+/// to handle those.
+///
+/// ```
+/// use sysexit;
+///
+/// assert!(sysexit::is_reserved(162));
+/// assert!(!sysexit::is_recognised(162));
+/// ```
+///
+/// [`sysexit::Unknown`]: enum.Code.html#variant.Unknown
+/// [`is_reserved`]: fn.is_reserved.html
+/// [`Signal`]: struct.Signal.html
+pub fn is_recognised(n: i32) -> bool {
+    Code::from(n) as i32 == n
+}
+
+/// Test if provided exit code is valid, that is within the 0–255 (inclusive)
+/// range.
+pub fn is_valid(n: i32) -> bool {
+    (0..=255).contains(&n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sigbase() {
+        assert_eq!(SIGBASE, 128);
+    }
+
+    #[test]
+    fn from_i32() {
+        assert_eq!(Code::from(0), Success);
+        assert_eq!(Code::from(1), Failure);
+        assert_eq!(Code::from(2), Unknown);
+
+        assert_eq!(Code::from(64), Usage);
+        assert_eq!(Code::from(65), DataErr);
+        assert_eq!(Code::from(66), NoInput);
+        assert_eq!(Code::from(67), NoUser);
+        assert_eq!(Code::from(68), NoHost);
+        assert_eq!(Code::from(69), Unavailable);
+        assert_eq!(Code::from(70), Software);
+        assert_eq!(Code::from(71), OsErr);
+        assert_eq!(Code::from(72), OsFile);
+        assert_eq!(Code::from(73), CantCreat);
+        assert_eq!(Code::from(74), IoErr);
+        assert_eq!(Code::from(75), TempFail);
+        assert_eq!(Code::from(76), Protocol);
+        assert_eq!(Code::from(77), NoPerm);
+        assert_eq!(Code::from(78), Config);
+
+        assert_eq!(Code::from(126), NotExecutable);
+        assert_eq!(Code::from(127), NotFound);
+
+        assert_eq!(Code::from(129), SIGHUP);
+        assert_eq!(Code::from(130), SIGINT);
+        assert_eq!(Code::from(131), SIGQUIT);
+        assert_eq!(Code::from(132), SIGILL);
+        assert_eq!(Code::from(133), SIGTRAP);
+        assert_eq!(Code::from(134), SIGABRT);
+        assert_eq!(Code::from(135), SIGBUS);
+        assert_eq!(Code::from(136), SIGFPE);
+        assert_eq!(Code::from(137), SIGKILL);
+        assert_eq!(Code::from(139), SIGSEGV);
+        assert_eq!(Code::from(138), SIGUSR1);
+        assert_eq!(Code::from(140), SIGUSR2);
+        assert_eq!(Code::from(141), SIGPIPE);
+        assert_eq!(Code::from(142), SIGALRM);
+        assert_eq!(Code::from(143), SIGTERM);
+        assert_eq!(Code::from(154), SIGVTALRM);
+
+        assert_eq!(Code::from(-1), Unknown);
+        assert_eq!(Code::from(128), Signaled);
+        assert_eq!(Code::from(162), Unknown);
+        assert_eq!(Code::from(192), Unknown);
+        assert_eq!(Code::from(193), Unknown);
+    }
+
+    #[cfg(feature = "std")]
+    fn exit_status(code: i32) -> process::ExitStatus {
+        process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("exit {}", code))
+            .status()
+            .expect("failed to run sh(1)")
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_exitstatus() {
+        assert_eq!(Code::from(exit_status(0)), Success);
+        assert_eq!(Code::from(exit_status(1)), Failure);
+        assert_eq!(Code::from(exit_status(2)), Unknown);
+
+        assert_eq!(Code::from(exit_status(64)), Usage);
+        assert_eq!(Code::from(exit_status(65)), DataErr);
+        assert_eq!(Code::from(exit_status(66)), NoInput);
+        assert_eq!(Code::from(exit_status(67)), NoUser);
+        assert_eq!(Code::from(exit_status(68)), NoHost);
+        assert_eq!(Code::from(exit_status(69)), Unavailable);
+        assert_eq!(Code::from(exit_status(70)), Software);
+        assert_eq!(Code::from(exit_status(71)), OsErr);
+        assert_eq!(Code::from(exit_status(72)), OsFile);
+        assert_eq!(Code::from(exit_status(73)), CantCreat);
+        assert_eq!(Code::from(exit_status(74)), IoErr);
+        assert_eq!(Code::from(exit_status(75)), TempFail);
+        assert_eq!(Code::from(exit_status(76)), Protocol);
+        assert_eq!(Code::from(exit_status(77)), NoPerm);
+        assert_eq!(Code::from(exit_status(78)), Config);
+
+        assert_eq!(Code::from(exit_status(126)), NotExecutable);
+        assert_eq!(Code::from(exit_status(127)), NotFound);
+
+        assert_eq!(Code::from(exit_status(129)), SIGHUP);
+        assert_eq!(Code::from(exit_status(130)), SIGINT);
+        assert_eq!(Code::from(exit_status(137)), SIGKILL);
+        assert_eq!(Code::from(exit_status(138)), SIGUSR1);
+        assert_eq!(Code::from(exit_status(140)), SIGUSR2);
+        assert_eq!(Code::from(exit_status(141)), SIGPIPE);
+        assert_eq!(Code::from(exit_status(142)), SIGALRM);
+        assert_eq!(Code::from(exit_status(143)), SIGTERM);
+        assert_eq!(Code::from(exit_status(154)), SIGVTALRM);
+    }
+
+    #[cfg(feature = "std")]
+    fn signal_status(signal: &str) -> process::ExitStatus {
+        process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("kill -{} $$", signal))
+            .status()
+            .expect("failed to run sh(1)")
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn coarse() {
+        assert_eq!(from_status_coarse(exit_status(0)), Success);
+        assert_eq!(from_status_coarse(exit_status(74)), IoErr);
+        assert_eq!(from_status_coarse(exit_status(127)), NotFound);
+
+        assert_eq!(from_status_coarse(exit_status(129)), Signaled);
+        assert_eq!(from_status_coarse(exit_status(143)), Signaled);
+        assert_eq!(from_status_coarse(signal_status("HUP")), Signaled);
+        assert_eq!(from_status_coarse(signal_status("TERM")), Signaled);
+        assert_eq!(from_status_coarse(signal_status("KILL")), Signaled);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_signaled_exitstatus() {
+        assert_eq!(Code::from(signal_status("HUP")), SIGHUP);
+        assert_eq!(Code::from(signal_status("INT")), SIGINT);
+        assert_eq!(Code::from(signal_status("KILL")), SIGKILL);
+        assert_eq!(Code::from(signal_status("TERM")), SIGTERM);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    #[cfg(feature = "std")]
+    fn real_signal() {
+        assert_eq!(prefer_real_signal(exit_status(0)), Success);
+        assert_eq!(prefer_real_signal(exit_status(74)), IoErr);
+        assert_eq!(prefer_real_signal(exit_status(127)), NotFound);
+
+        // a shell reporting the signal that terminated its child
+        assert_eq!(prefer_real_signal(exit_status(129)), SIGHUP);
+        assert_eq!(prefer_real_signal(exit_status(143)), SIGTERM);
+        assert_eq!(prefer_real_signal(exit_status(162)), Unknown);
+
+        // the raw wait status of a signal termination is the bare signal
+        // number, which read as an exit code disagrees with 128 + N
+        use std::os::unix::process::ExitStatusExt;
+        for &(signal, code) in &[
+            (libc::SIGHUP, SIGHUP),
+            (libc::SIGUSR1, SIGUSR1),
+            (libc::SIGTERM, SIGTERM),
+        ] {
+            let status = process::ExitStatus::from_raw(signal);
+            assert_ne!(Code::from(status.into_raw()), code);
+            assert_eq!(prefer_real_signal(status), code);
+        }
+        assert_eq!(prefer_real_signal(signal_status("HUP")), SIGHUP);
+        assert_eq!(prefer_real_signal(signal_status("TERM")), SIGTERM);
+        assert_eq!(prefer_real_signal(signal_status("KILL")), SIGKILL);
+    }
+
+    #[test]
+    fn unknown_raw() {
+        assert_eq!(unknown_with_raw(42).to_string(), "unknown exit code (42)");
+        assert_eq!(unknown_with_raw(-1).to_string(), "unknown exit code (-1)");
+        assert!(unknown_with_raw(300).to_string().contains("300"));
+    }
+
+    #[cfg(feature = "std")]
+    fn child_status(action: &str) -> process::ExitStatus {
+        process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "tests::child", "--test-threads=1"])
+            .env("SYSEXIT_TEST_CHILD", action)
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::null())
+            .status()
+            .expect("failed to run test binary")
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn child() {
+        match std::env::var("SYSEXIT_TEST_CHILD") {
+            Ok(ref action) if action == "exit" => IoErr.exit(),
+            Ok(ref action) if action == "panic" => {
+                install_panic_hook();
+                panic!("panic in child");
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn exit() {
+        assert_eq!(from_status(child_status("exit")), IoErr);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn panic_hook() {
+        assert_eq!(from_status(child_status("panic")), Software);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn wrapper_ok() {
+        let mut stderr = Vec::new();
+        assert_eq!(report(|| Ok::<(), Code>(()), &mut stderr), Success);
+        assert!(stderr.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn wrapper_err() {
+        let mut stderr = Vec::new();
+        let code = report(
+            || {
+                Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "cannot open file",
+                ))
+            },
+            &mut stderr,
+        );
+        assert_eq!(code, NoPerm);
+        assert_eq!(
+            String::from_utf8(stderr).unwrap(),
+            "error: cannot open file\n"
+        );
+
+        let mut stderr = Vec::new();
+        assert_eq!(report(|| Err(DataErr), &mut stderr), DataErr);
+        assert_eq!(String::from_utf8(stderr).unwrap(), "error: data (65)\n");
+
+        // the error path of main_wrapper would print to the real stderr
+        assert_eq!(main_wrapper(|| Ok::<(), Code>(())), Success);
+    }
+
+    #[test]
+    fn default_profile() {
+        let c = classify_with_profile(0, Profile::Default);
+        assert_eq!((c.code, c.is_error, c.reason), (Success, false, "success"));
+        let c = classify_with_profile(1, Profile::Default);
+        assert_eq!((c.code, c.is_error, c.reason), (Failure, true, "failure"));
+        let c = classify_with_profile(74, Profile::Default);
+        assert_eq!((c.code, c.is_error, c.reason), (IoErr, true, "i/o error"));
+    }
+
+    #[test]
+    fn grep_profile() {
+        let c = classify_with_profile(0, Profile::Grep);
+        assert_eq!((c.code, c.is_error, c.reason), (Success, false, "matched"));
+        let c = classify_with_profile(1, Profile::Grep);
+        assert_eq!((c.code, c.is_error, c.reason), (Failure, false, "no match"));
+        let c = classify_with_profile(2, Profile::Grep);
+        assert_eq!((c.code, c.is_error, c.reason), (Unknown, true, "error"));
+        assert_eq!(c.to_string(), "error (2)");
+
+        assert_eq!(grep_outcome(0), GrepOutcome::Matched);
+        assert_eq!(grep_outcome(1), GrepOutcome::NoMatch);
+        assert_eq!(grep_outcome(2), GrepOutcome::Error);
+        assert_eq!(grep_outcome(130), GrepOutcome::Error);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn error_kinds() {
+        for &code in ALL {
+            for &kind in error_kinds_for(code) {
+                assert_eq!(codes_for_error_kind(kind), code);
+            }
+        }
+
+        assert_eq!(error_kinds_for(NoPerm), &[io::ErrorKind::PermissionDenied]);
+        assert_eq!(error_kinds_for(Protocol).len(), 5);
+        assert!(error_kinds_for(Success).is_empty());
+        assert!(error_kinds_for(SIGTERM).is_empty());
+    }
+
+    #[test]
+    #[cfg(sysexit_sigemt)]
+    fn sigemt() {
+        assert_eq!(Code::from(SIGBASE + 7), SIGEMT);
+        assert_eq!(
+            SIGEMT.to_string(),
+            format!("emulator trap signal ({})", SIGEMT as i32)
+        );
+    }
+
+    #[test]
+    #[cfg(sysexit_siglost)]
+    fn siglost() {
+        assert_eq!(Code::from(SIGBASE + libc::SIGLOST), SIGLOST);
+        assert_eq!(
+            SIGLOST.to_string(),
+            format!("resource lost signal ({})", SIGLOST as i32)
+        );
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    #[cfg(feature = "std")]
+    fn wrapper_exit() {
+        use std::os::unix::process::ExitStatusExt;
+
+        assert_eq!(wrapper_exit_code(exit_status(0)), 0);
+        assert_eq!(wrapper_exit_code(exit_status(74)), 74);
+        assert_eq!(wrapper_exit_code(exit_status(3)), 3);
+        assert_eq!(wrapper_exit_code(signal_status("TERM")), 143);
+
+        // stopped, and continued, as reported by waitpid(2)
+        let stopped = process::ExitStatus::from_raw((libc::SIGTSTP << 8) | 0x7f);
+        assert_eq!(wrapper_exit_code(stopped), SIGBASE + libc::SIGTSTP);
+        let continued = process::ExitStatus::from_raw(0xffff);
+        assert_eq!(wrapper_exit_code(continued), 2);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", windows))]
+    fn wrapper_exit_windows() {
+        use std::os::windows::process::ExitStatusExt;
+
+        let status = |n: u32| process::ExitStatus::from_raw(n);
+        assert_eq!(wrapper_exit_code(status(74)), 74);
+        assert_eq!(wrapper_exit_code(status(300)), 2);
+        assert_eq!(wrapper_exit_code(status(0xC000_0005)), 139);
+        assert_eq!(wrapper_exit_code(status(0xC000_0135)), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn shell_message() {
+        assert_eq!(
+            infer_from_shell_message("bash: foo: command not found"),
+            Some(NotFound)
+        );
+        assert_eq!(
+            infer_from_shell_message("zsh: command not found: foo"),
+            Some(NotFound)
+        );
+        assert_eq!(
+            infer_from_shell_message("sh: 1: foo: not found"),
+            Some(NotFound)
+        );
+        assert_eq!(
+            infer_from_shell_message("bash: ./foo: Permission denied"),
+            Some(NotExecutable)
+        );
+        assert_eq!(
+            infer_from_shell_message("bash: ./foo: cannot execute binary file"),
+            Some(NotExecutable)
+        );
+        assert_eq!(
+            infer_from_shell_message("cat: foo: No such file or directory"),
+            Some(OsFile)
+        );
+        assert_eq!(infer_from_shell_message("hello world"), None);
+        assert_eq!(infer_from_shell_message(""), None);
+    }
+
+    #[test]
+    fn deterministic() {
+        assert!(Success.is_deterministic());
+        assert!(Usage.is_deterministic());
+        assert!(DataErr.is_deterministic());
+        assert!(Config.is_deterministic());
+        assert!(NotFound.is_deterministic());
+
+        assert!(!Failure.is_deterministic());
+        assert!(!Unknown.is_deterministic());
+        assert!(!TempFail.is_deterministic());
+        assert!(!Unavailable.is_deterministic());
+        assert!(!IoErr.is_deterministic());
+        assert!(!Signaled.is_deterministic());
+        assert!(!SIGKILL.is_deterministic());
+        assert!(!SIGTERM.is_deterministic());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn shell_exit() {
+        assert_eq!(Success.to_shell_exit(), "exit 0  # success");
+        assert_eq!(IoErr.to_shell_exit(), "exit 74  # i/o error");
+        assert_eq!(SIGKILL.to_shell_exit(), "exit 137  # kill signal (SIGKILL)");
+    }
+
+    #[test]
+    fn cargo_profile() {
+        let c = classify_with_profile(0, Profile::Cargo);
+        assert_eq!((c.code, c.is_error, c.reason), (Success, false, "success"));
+        let c = classify_with_profile(1, Profile::Cargo);
+        assert_eq!((c.code, c.is_error, c.reason), (Failure, true, "error"));
+        let c = classify_with_profile(101, Profile::Cargo);
+        assert_eq!(
+            (c.code, c.is_error, c.reason),
+            (Failure, true, "tests failed")
+        );
+        assert_eq!(c.to_string(), "tests failed (101)");
+
+        let c = classify_with_profile(101, Profile::Default);
+        assert_eq!((c.code, c.is_error, c.reason), (Unknown, true, "unknown"));
+    }
+
+    #[test]
+    fn meanings() {
+        for &code in &[Failure, Unknown, NotExecutable, NotFound, Signaled, SIGKILL] {
+            assert!(code.likely_meanings().len() > 1);
+        }
+        for &code in &[Success, Usage, DataErr, IoErr, Config, SIGTERM] {
+            assert_eq!(code.likely_meanings().len(), 1);
+        }
+        for &code in ALL {
+            assert!(code.to_string().starts_with(code.likely_meanings()[0]));
+        }
+    }
+
+    #[test]
+    fn signal_names() {
+        for &code in ALL {
+            let n = SIGNAL_NAMES.iter().filter(|&&(c, _)| c == code).count();
+            if code as i32 > SIGBASE {
+                assert_eq!(n, 1, "{:?}", code);
+            } else {
+                assert_eq!(n, 0, "{:?}", code);
+            }
+        }
+        assert_eq!(SIGTERM.signal_name(), Some("SIGTERM"));
+        assert_eq!(SIGKILL.signal_name(), Some("SIGKILL"));
+        assert_eq!(IoErr.signal_name(), None);
+        assert_eq!(Signaled.signal_name(), None);
+    }
+
+    #[test]
+    fn shell_profile() {
+        for &shell in &[ShellKind::Bash, ShellKind::Dash, ShellKind::Zsh] {
+            let c = classify_with_profile(126, Profile::Shell(shell));
+            assert_eq!(
+                (c.code, c.reason),
+                (NotExecutable, "command not executable")
+            );
+            let c = classify_with_profile(127, Profile::Shell(shell));
+            assert_eq!((c.code, c.reason), (NotFound, "command not found"));
+        }
+
+        let c = classify_with_profile(74, Profile::Shell(ShellKind::Dash));
+        assert_eq!((c.code, c.is_error, c.reason), (IoErr, true, "i/o error"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn display_roundtrip() {
+        for &code in all() {
+            assert!(code.roundtrips_via_display(), "{:?}", code);
+        }
+        assert_eq!("i/o error (74)".parse(), Ok(IoErr));
+        assert_eq!("termination signal (143)".parse(), Ok(SIGTERM));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn to_error_kind() {
+        use std::io::ErrorKind;
+
+        for &kind in &[
+            ErrorKind::NotFound,
+            ErrorKind::PermissionDenied,
+            ErrorKind::AddrNotAvailable,
+            ErrorKind::ConnectionReset,
+            ErrorKind::AlreadyExists,
+            ErrorKind::InvalidData,
+        ] {
+            assert_eq!(ErrorKind::from(Code::from(kind)), kind);
+        }
+        for &code in &[Usage, Config, Software, SIGTERM] {
+            assert_eq!(ErrorKind::from(code), ErrorKind::Other);
+        }
+
+        let err = NoPerm.to_io_error();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert_eq!(err.to_string(), NoPerm.to_string());
+        assert_eq!(err.as_exit_code(), NoPerm);
+    }
+
+    #[test]
+    fn signal_numbers_roundtrip() {
+        for &(code, _) in SIGNAL_NAMES {
+            let n = code.signal_number().unwrap();
+            assert!(code.is_signal(), "{:?}", code);
+            assert_eq!(code.category(), Category::Signal);
+            assert_eq!(Code::from(SIGBASE + n), code);
+        }
+        assert_eq!(SIGHUP.signal_number(), Some(1));
+        assert_eq!(SIGINT.signal_number(), Some(2));
+        assert_eq!(SIGKILL.signal_number(), Some(9));
+        assert_eq!(SIGTERM.signal_number(), Some(15));
+
+        for &code in &[
+            Success,
+            Failure,
+            Unknown,
+            Usage,
+            Config,
+            NotExecutable,
+            NotFound,
+            Signaled,
+        ] {
+            assert!(!code.is_signal(), "{:?}", code);
+            assert_eq!(code.signal_number(), None);
+        }
+    }
+
+    #[test]
+    fn categories() {
+        assert_eq!(Success.category(), Category::Success);
+        assert_eq!(Failure.category(), Category::Generic);
+        assert_eq!(Unknown.category(), Category::Generic);
+        assert_eq!(Usage.category(), Category::SysExit);
+        assert_eq!(Config.category(), Category::SysExit);
+        assert_eq!(NotExecutable.category(), Category::Shell);
+        assert_eq!(Signaled.category(), Category::Shell);
+        assert_eq!(SIGKILL.category(), Category::Signal);
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(try_from_i32(-1), Err(TryFromCodeError::OutOfRange(-1)));
+        assert_eq!(try_from_i32(0), Ok(Success));
+        assert_eq!(try_from_i32(255), Ok(Unknown));
+        assert_eq!(try_from_i32(256), Err(TryFromCodeError::OutOfRange(256)));
+        assert_eq!(try_from_i32(50), Ok(Unknown));
+
+        assert_eq!(
+            try_from_i32_strict(-1),
+            Err(TryFromCodeError::OutOfRange(-1))
+        );
+        assert_eq!(try_from_i32_strict(0), Ok(Success));
+        assert_eq!(
+            try_from_i32_strict(255),
+            Err(TryFromCodeError::Unrecognised(255))
+        );
+        assert_eq!(
+            try_from_i32_strict(256),
+            Err(TryFromCodeError::OutOfRange(256))
+        );
+        assert_eq!(
+            try_from_i32_strict(50),
+            Err(TryFromCodeError::Unrecognised(50))
+        );
+        assert_eq!(try_from_i32_strict(2), Ok(Unknown));
+        assert_eq!(try_from_i32_strict(128), Ok(Signaled));
+        assert_eq!(try_from_i32_strict(143), Ok(SIGTERM));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn try_from_error_display() {
+        assert_eq!(
+            TryFromCodeError::OutOfRange(-1).to_string(),
+            "exit code out of range: -1"
+        );
+        assert_eq!(
+            TryFromCodeError::Unrecognised(50).to_string(),
+            "unrecognised exit code: 50"
+        );
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(Success.name(), "EX_OK");
+        assert_eq!(Usage.name(), "EX_USAGE");
+        assert_eq!(Config.name(), "EX_CONFIG");
+        assert_eq!(Failure.name(), "FAILURE");
+        assert_eq!(Unknown.name(), "UNKNOWN");
+        assert_eq!(NotExecutable.name(), "NOEXEC");
+        assert_eq!(NotFound.name(), "NOTFOUND");
+        assert_eq!(Signaled.name(), "SIGNALED");
+        assert_eq!(SIGKILL.name(), "SIGKILL");
+        for &code in ALL {
+            let name = code.name();
+            assert!(!name.contains(' '), "{:?}", name);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn parse_names() {
+        for &code in ALL {
+            let name = code.name();
+            assert_eq!(name.parse(), Ok(code));
+            assert_eq!(name.to_lowercase().parse(), Ok(code));
+        }
+        assert_eq!("EX_USAGE".parse(), Ok(Usage));
+        assert_eq!("ex_dataerr".parse(), Ok(DataErr));
+        assert_eq!("Ex_IoErr".parse(), Ok(IoErr));
+        assert_eq!("SIGTERM".parse(), Ok(SIGTERM));
+        assert_eq!("sigkill".parse(), Ok(SIGKILL));
+
+        for s in &[
+            "",
+            "EX_",
+            "EX_NONSENSE",
+            "USAGE",
+            "TERM",
+            "SIG",
+            " EX_USAGE",
+            "74",
+        ] {
+            let err = s.parse::<Code>().unwrap_err();
+            assert_eq!(err.to_string(), format!("unrecognised exit code: {:?}", s));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn display_parse_error() {
+        for s in &["", "i/o error", "i/o error (75)", "i/o error (x)", "(74)"] {
+            let err = s.parse::<Code>().unwrap_err();
+            assert_eq!(err.to_string(), format!("unrecognised exit code: {:?}", s));
+        }
+    }
+
+    #[test]
+    fn http_status() {
+        assert_eq!(Success.to_http_status(), 200);
+        assert_eq!(Usage.to_http_status(), 400);
+        assert_eq!(DataErr.to_http_status(), 400);
+        assert_eq!(NoPerm.to_http_status(), 403);
+        assert_eq!(NoInput.to_http_status(), 404);
+        assert_eq!(Protocol.to_http_status(), 502);
+        assert_eq!(Unavailable.to_http_status(), 503);
+        assert_eq!(TempFail.to_http_status(), 503);
+        assert_eq!(Software.to_http_status(), 500);
+        assert_eq!(OsErr.to_http_status(), 500);
+        assert_eq!(SIGKILL.to_http_status(), 500);
+        assert_eq!(SIGTERM.to_http_status(), 500);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    #[cfg(feature = "std")]
+    fn oversized_code_unix() {
+        let status = exit_status(300);
+        assert_eq!(status.code(), Some(300 & 0xff));
+        assert_eq!(had_oversized_code(status), None);
+        assert_eq!(had_oversized_code(exit_status(74)), None);
+    }
+
+    #[test]
+    fn ntstatus_codes() {
+        assert_eq!(ntstatus_code(0xC000_0005_u32 as i32), Some(SIGSEGV));
+        assert_eq!(ntstatus_code(0xC000_00FD_u32 as i32), Some(SIGSEGV));
+        assert_eq!(ntstatus_code(0xC000_001D_u32 as i32), Some(SIGILL));
+        assert_eq!(ntstatus_code(0xC000_013A_u32 as i32), Some(SIGINT));
+        assert_eq!(ntstatus_code(0xC000_0409_u32 as i32), Some(SIGABRT));
+        assert_eq!(ntstatus_code(-1073741819), Some(SIGSEGV));
+        assert_eq!(ntstatus_code(0), None);
+        assert_eq!(ntstatus_code(74), None);
+        assert_eq!(ntstatus_code(-1), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", windows))]
+    fn from_ntstatus_exitstatus() {
+        use std::os::windows::process::ExitStatusExt;
+
+        let status = |n: u32| process::ExitStatus::from_raw(n);
+        assert_eq!(from_status(status(0xC000_0005)), SIGSEGV);
+        assert_eq!(from_status(status(0xC000_00FD)), SIGSEGV);
+        assert_eq!(from_status(status(0xC000_001D)), SIGILL);
+        assert_eq!(from_status(status(0xC000_013A)), SIGINT);
+        assert_eq!(from_status(status(74)), IoErr);
+        assert_eq!(from_status(status(0xC000_0135)), Unknown);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", windows))]
+    fn diagnosis_ntstatus() {
+        use std::os::windows::process::ExitStatusExt;
+
+        let diagnosis = Diagnosis::from_status(process::ExitStatus::from_raw(0xC000_0005));
+        assert_eq!(diagnosis.code, SIGSEGV);
+        assert_eq!(diagnosis.signal, Some(11));
+        assert_eq!(
+            diagnosis.message,
+            "terminated by exception 0xC0000005, as if by signal 11 (SIGSEGV)"
+        );
+
+        let diagnosis = Diagnosis::from_status(process::ExitStatus::from_raw(0xC000_0017));
+        assert_eq!(diagnosis.code, OsErr);
+        assert_eq!(diagnosis.signal, None);
+        assert_eq!(diagnosis.message, "terminated by exception 0xC0000017");
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", windows))]
+    fn oversized_code_windows() {
+        let exit_status = |code: i32| {
+            process::Command::new("cmd")
+                .arg("/C")
+                .arg(format!("exit {}", code))
+                .status()
+                .expect("failed to run cmd")
+        };
+        assert_eq!(had_oversized_code(exit_status(300)), Some(300));
+        assert_eq!(had_oversized_code(exit_status(74)), None);
+
+        use std::os::windows::process::ExitStatusExt;
+        let status = |n: u32| process::ExitStatus::from_raw(n);
+        assert_eq!(had_oversized_code(status(0xC000_0005)), None);
+        assert_eq!(had_oversized_code(status(0xC000_001D)), None);
+        assert_eq!(
+            had_oversized_code(status(0xC000_0135)),
+            Some(0xC000_0135_u32 as i32)
+        );
+    }
+
+    #[test]
+    fn hex_colors() {
+        for &code in ALL {
+            let color = code.hex_color();
+            assert_eq!(color.len(), 7);
+            assert!(color.starts_with('#'));
+            assert!(color[1..].chars().all(|c| c.is_ascii_hexdigit()));
+        }
+        assert_eq!(Success.hex_color(), "#2ecc71");
+        assert_eq!(Failure.hex_color(), "#e67e22");
+        assert_eq!(IoErr.hex_color(), "#e74c3c");
+        assert_eq!(NotFound.hex_color(), "#9b59b6");
+        assert_eq!(SIGTERM.hex_color(), "#c0392b");
+    }
+
+    #[test]
+    fn change_outcome() {
+        assert_eq!(from_change_outcome(false, None), 0);
+        assert_eq!(from_change_outcome(true, None), 2);
+        assert_eq!(from_change_outcome(false, Some(Config)), 78);
+        assert_eq!(from_change_outcome(true, Some(IoErr)), 74);
+    }
+
+    #[test]
+    fn fatal_for_batch() {
+        assert!(Usage.is_fatal_for_batch());
+        assert!(Config.is_fatal_for_batch());
+        assert!(SIGINT.is_fatal_for_batch());
+        assert!(SIGTERM.is_fatal_for_batch());
+
+        assert!(!Success.is_fatal_for_batch());
+        assert!(!DataErr.is_fatal_for_batch());
+        assert!(!NoInput.is_fatal_for_batch());
+        assert!(!IoErr.is_fatal_for_batch());
+        assert!(!SIGPIPE.is_fatal_for_batch());
+    }
+
+    #[test]
+    fn wide() {
+        assert_eq!(from_wide(0), Success);
+        assert_eq!(from_wide(74), IoErr);
+        assert_eq!(from_wide(143), SIGTERM);
+        assert_eq!(from_wide(255), Unknown);
+
+        assert_eq!(from_wide(-1), Unknown);
+        assert_eq!(from_wide(256 + 74), Unknown);
+        assert_eq!(from_wide(i64::MAX as i128 + 75), Unknown);
+        assert_eq!(from_wide(i128::MAX), Unknown);
+        assert_eq!(from_wide(i128::MIN), Unknown);
+    }
+
+    #[test]
+    fn discriminant_list() {
+        let list = discriminants();
+        assert_eq!(list.len(), ALL.len());
+        assert!(list.windows(2).all(|w| w[0] < w[1]));
+        assert!(list.iter().all(|&n| is_valid(n)));
+        for &code in ALL {
+            assert!(list.contains(&(code as i32)));
+        }
+    }
+
+    #[test]
+    fn help_urls() {
+        for &code in ALL {
+            if Usage as i32 <= code as i32 && code as i32 <= Config as i32 {
+                let url = code.help_url().unwrap();
+                assert!(url.starts_with("https://man.openbsd.org/sysexits.3#EX_"));
+            }
+        }
+        assert_eq!(
+            IoErr.help_url(),
+            Some("https://man.openbsd.org/sysexits.3#EX_IOERR")
+        );
+        assert_eq!(SIGTERM.help_url(), Some("https://man.openbsd.org/signal.3"));
+        assert_eq!(Unknown.help_url(), None);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    #[cfg(feature = "std")]
+    fn success_with() {
+        let extra = &[3, 75, SIGBASE + libc::SIGPIPE];
+        assert!(is_success_with(exit_status(0), extra));
+        assert!(is_success_with(exit_status(0), &[]));
+        assert!(is_success_with(exit_status(3), extra));
+        assert!(is_success_with(exit_status(75), extra));
+        assert!(is_success_with(signal_status("PIPE"), extra));
+
+        assert!(!is_success_with(exit_status(1), extra));
+        assert!(!is_success_with(exit_status(4), extra));
+        assert!(!is_success_with(exit_status(74), extra));
+        assert!(!is_success_with(exit_status(3), &[]));
+        assert!(!is_success_with(signal_status("TERM"), extra));
+
+        // unrecognised codes are not confused with Unknown (2)
+        assert!(!is_success_with(exit_status(3), &[2]));
+        assert!(!is_success_with(exit_status(2), extra));
+        assert!(is_success_with(exit_status(2), &[2]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn diagnosis_failure() {
+        use std::error::Error;
+
+        let diagnosis = Diagnosis::from_status(exit_status(74));
+        assert_eq!(diagnosis.code, IoErr);
+        assert_eq!(diagnosis.signal, None);
+        assert_eq!(
+            diagnosis.to_string(),
+            "i/o error (74): exited with status 74"
+        );
+        assert!(diagnosis.source().is_none());
+        assert_eq!(diagnosis.as_exit_code(), IoErr);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    #[cfg(feature = "std")]
+    fn diagnosis_signal() {
+        use std::error::Error;
+
+        let diagnosis = Diagnosis::from_status(signal_status("TERM"));
+        assert_eq!(diagnosis.code, SIGTERM);
+        assert_eq!(diagnosis.signal, Some(libc::SIGTERM));
+        assert_eq!(
+            diagnosis.to_string(),
+            format!(
+                "termination signal (143): terminated by signal {} (SIGTERM)",
+                libc::SIGTERM
+            )
+        );
+        assert!(diagnosis.source().is_none());
+    }
+
+    #[test]
+    fn cacheable_failure() {
+        assert!(DataErr.is_cacheable_failure());
+        assert!(Usage.is_cacheable_failure());
+        assert!(NotFound.is_cacheable_failure());
+
+        assert!(!Success.is_cacheable_failure());
+        assert!(!TempFail.is_cacheable_failure());
+        assert!(!Unavailable.is_cacheable_failure());
+        assert!(!SIGKILL.is_cacheable_failure());
+        assert!(!SIGINT.is_cacheable_failure());
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    #[cfg(feature = "std")]
+    fn errno_codes() {
+        assert_eq!(from_errno(libc::EACCES), NoPerm);
+        assert_eq!(from_errno(libc::ENOENT), OsFile);
+        assert_eq!(from_errno(libc::EEXIST), CantCreat);
+        assert_eq!(from_errno(libc::EIO), IoErr);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", windows))]
+    fn errno_codes_windows() {
+        // ERROR_FILE_NOT_FOUND and ERROR_ACCESS_DENIED
+        assert_eq!(from_errno(2), OsFile);
+        assert_eq!(from_errno(5), NoPerm);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    #[cfg(feature = "errno")]
+    fn from_errno_crate() {
+        assert_eq!(Code::from(errno::Errno(libc::EACCES)), NoPerm);
+        assert_eq!(Code::from(errno::Errno(libc::ENOENT)), OsFile);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_json_roundtrip() {
+        for &code in ALL {
+            let json = serde_json::to_string(&code).unwrap();
+            assert_eq!(serde_json::from_str::<Code>(&json).unwrap(), code);
+        }
+        assert_eq!(serde_json::to_string(&IoErr).unwrap(), "\"EX_IOERR\"");
+        assert_eq!(serde_json::to_string(&SIGTERM).unwrap(), "\"SIGTERM\"");
+        assert_eq!(serde_json::from_str::<Code>("\"ex_usage\"").unwrap(), Usage);
+
+        let err = serde_json::from_str::<Code>("\"EX_NONSENSE\"").unwrap_err();
+        assert!(
+            err.to_string().contains("unrecognised exit code"),
+            "{}",
+            err
+        );
+        assert!(serde_json::from_str::<Code>("74").is_err());
+    }
+
+    #[test]
+    fn prompt_indicators() {
+        assert_eq!(Success.prompt_indicator(), "");
+        assert_eq!(Failure.prompt_indicator(), "✗");
+        assert_eq!(IoErr.prompt_indicator(), "✗");
+        assert_eq!(NotFound.prompt_indicator(), "✗");
+        assert_eq!(Signaled.prompt_indicator(), "⚡");
+        assert_eq!(SIGINT.prompt_indicator(), "⚡");
+        assert_eq!(SIGTERM.prompt_indicator(), "⚡");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn termination() {
+        use std::process::{ExitCode, Termination};
+
+        assert_eq!(Success.report(), ExitCode::SUCCESS);
+        assert_eq!(Success.report(), ExitCode::from(0));
+        assert_eq!(Failure.report(), ExitCode::FAILURE);
+        assert_eq!(DataErr.report(), ExitCode::from(65));
+        assert_eq!(SIGTERM.report(), ExitCode::from(143));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn summary() {
+        let (code, summary) = summarize(&[Success, IoErr, Success, SIGTERM, Success]);
+        assert_eq!(code, SIGTERM);
+        assert_eq!(summary, "3 succeeded, 1 i/o error, 1 killed by SIGTERM");
+
+        let (code, summary) = summarize(&[Failure, DataErr, NoInput, DataErr]);
+        assert_eq!(code, DataErr);
+        assert_eq!(summary, "1 failure, 2 data, 1 no input");
+
+        let (code, summary) = summarize(&[Success, Success]);
+        assert_eq!(code, Success);
+        assert_eq!(summary, "2 succeeded");
+
+        assert_eq!(
+            summarize(&[]),
+            (Success, String::from("nothing to summarize"))
+        );
+    }
+
+    #[test]
+    fn signal_roundtrip() {
+        for n in SIGBASE + 1..=SIGBASE + SIGMAX {
+            let signal = Signal::from_exit_code(n).unwrap();
+            assert_eq!(signal.to_i32(), n);
+            assert_eq!(i32::from(signal), n);
+            assert_eq!(signal.number(), n - SIGBASE);
+            assert!(is_reserved(n));
+            match signal.code() {
+                Some(code) => {
+                    assert_eq!(code as i32, n);
+                    assert_eq!(code.signal_number(), Some(signal.number()));
+                }
+                None => assert_eq!(Code::from(n), Unknown),
+            }
+        }
+        assert_eq!(Signal::from_exit_code(143).unwrap().code(), Some(SIGTERM));
+        assert_eq!(Signal::from_exit_code(162).unwrap().code(), None);
+
+        assert_eq!(Signal::from_exit_code(SIGBASE), None);
+        assert_eq!(Signal::from_exit_code(SIGBASE + SIGMAX + 1), None);
+        assert_eq!(Signal::from_exit_code(i32::MAX), None);
+        assert_eq!(Signal::from_exit_code(i32::MIN), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn signal_from_status() {
+        let signal = |status| Signal::from_status(status).map(Signal::number);
+        assert_eq!(signal(signal_status("TERM")), Some(15));
+        assert_eq!(signal(signal_status("KILL")), Some(9));
+        assert_eq!(signal(exit_status(162)), Some(34));
+        assert_eq!(signal(exit_status(74)), None);
+        assert_eq!(signal(exit_status(128)), None);
+
+        let signal = Signal::from_exit_code(162).unwrap();
+        assert_eq!(signal.to_string(), "signal 34 (162)");
+        assert_eq!(
+            Signal::from_exit_code(143).unwrap().to_string(),
+            SIGTERM.to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn markdown_row() {
+        assert_eq!(
+            Success.to_markdown_row(),
+            "| 0 | EX_OK | success | success |"
+        );
+        assert_eq!(
+            IoErr.to_markdown_row(),
+            "| 74 | EX_IOERR | system | i/o error |"
+        );
+        assert_eq!(
+            NotFound.to_markdown_row(),
+            "| 127 | NOTFOUND | shell | not found |"
+        );
+        assert_eq!(
+            SIGTERM.to_markdown_row(),
+            "| 143 | SIGTERM | signal | termination signal |"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn markdown_rows() {
+        let table = markdown_table();
+        let mut lines = table.lines();
+        assert_eq!(
+            lines.next(),
+            Some("| Code | Name | Category | Description |")
+        );
+        assert_eq!(lines.next(), Some("|---|---|---|---|"));
+        assert_eq!(lines.count(), ALL.len());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn success() {
+        assert!(is_success(exit_status(0)));
+        assert!(!is_success(exit_status(1)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn error() {
+        assert!(is_error(exit_status(1)));
+        assert!(!is_error(exit_status(0)));
+    }
+
+    #[test]
+    fn reserved() {
+        for n in 0..512 {
+            println!("{}", n);
+            match n {
+                0..=2 => assert!(is_reserved(n)),
+                64..=78 => assert!(is_reserved(n)),
+                126..=192 => assert!(is_reserved(n)),
+                n => assert!(!is_reserved(n)),
+            }
+        }
+    }
+
+    #[test]
+    fn recognised() {
+        for n in -1..512 {
+            let expected = ALL.iter().any(|&code| code as i32 == n);
+            assert_eq!(is_recognised(n), expected);
+            if is_recognised(n) {
+                assert!(is_reserved(n));
+            }
+        }
+
+        assert!(is_recognised(2));
+        assert!(is_recognised(74));
+        assert!(is_recognised(143));
+
+        assert!(is_recognised(128));
+
+        // signal without a code
+        assert!(is_reserved(162));
+        assert!(!is_recognised(162));
+    }
+
+    #[test]
+    #[cfg(feature = "fuzz")]
+    fn fuzz_smoke() {
+        fuzz_classify(&[]);
+        fuzz_classify(&[0]);
+        fuzz_classify(&[74, 0, 0, 0, 143, 0, 0, 0]);
+        fuzz_classify(&[0xff; 64]);
+        fuzz_classify(b"i/o error (74)");
+        fuzz_classify(b"bash: foo: command not found");
+        fuzz_classify(&[0x80, 0xc0, 0xff, 0xfe, b'(', b')']);
+    }
 
     #[test]
     fn valid() {
         for n in 0..512 {
             match n {
-                0...255 => assert!(is_valid(n)),
+                0..=255 => assert!(is_valid(n)),
                 _ => assert!(!is_valid(n)),
             }
         }